@@ -1,26 +1,55 @@
-use crate::entities::Item;
+use crate::entities::{Item, Npc};
 use crate::game_event::GameEventType;
+use crate::game_over::GameOverCause;
 use crate::room::RoomType;
 use crate::sound::AudioEvent;
 
+/// What an item is used on, as in "use the crowbar on the rat".
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Target {
+    // The door of the current room that leads to this room.
+    Door(RoomType),
+    // Whatever enemy guards the current room.
+    Enemy,
+    Item(Item),
+    // A character in the current room.
+    Npc(Npc),
+    Terminal,
+    Casket,
+    Console,
+}
+
 pub enum ActionHandled {
     Handled,
     NotHandled,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Display)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Display, Serialize, Deserialize)]
 pub enum Action {
     // System Actions.
     Tick(u64),
     Message(String, GameEventType),
     Command(String),
     PlayerFinishedReading,
-    GameOver,
+    GameOver(GameOverCause),
+    Save(String),
+    Load(String),
 
     // Player
     Attack,
     Dodge,
     PickUp(Item),
+    Drop(Item),
+    Examine(Item),
+    // Look at a fixture of the current room, by its name.
+    ExamineFixture(String),
+    ExamineNpc(Npc),
+    ShowInventory,
+    // List the memories recovered so far.
+    ShowMemories,
+    Talk(Npc),
+    // Answer in the current conversation, numbered from 1.
+    Choose(usize),
     Enter(RoomType),
     Leave(RoomType),
 
@@ -30,24 +59,22 @@ pub enum Action {
     // Audio things
     Audio(AudioEvent),
 
-    //Pickup
-    PickUpKeycard,
-    PickUpCrowbar,
-
     // Game logic actions
-    PlayerDied,
+    PlayerDied(GameOverCause),
+    Breathe,
+    UpdateNpcs,
+    NpcDied(Npc),
 
     UseDoor,
-    UseKeycard,
-    UseCrowbar,
+    UseItem(Item),
+    UseOn(Item, Target),
     UseCasket,
     UseTerminal,
-
-    // Open Rooms
-    OpenCorridor,
-    OpenCryoControl,
+    UseConsole,
 
     ShowEnterText,
 
     Rebooted,
+    // Give the player a memory from `assets/memories.ron` back, by its id.
+    Remember(String),
 }
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::action::Action;
+use crate::game_event::GameEventType;
+use crate::trigger::Effect;
+use crate::utils::load_ron;
+use crate::App;
+
+// At full integrity the AI speaks clearly, at 0 it barely gets a word out.
+pub const FULL_INTEGRITY: i32 = 100;
+
+// Swapped in for letters the AI fails to get out.
+static GLITCHES: &'static [char] = &['#', '%', '&', '*', '/', '\\', '_', '~'];
+
+/// The ship's AI, as written down in `assets/ai.ron`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ShipAi {
+    // Integrity when a new game starts.
+    pub integrity: i32,
+    // Everything the AI says, by the id room texts and triggers use.
+    pub lines: HashMap<String, String>,
+}
+
+pub fn load_ai(path: &Path) -> io::Result<ShipAi> {
+    load_ron(path)
+}
+
+// The id in a `{ai:<id>}` line of a room text.
+fn voice_line_id(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.starts_with("{ai:") && line.ends_with('}') {
+        Some(&line[4..line.len() - 1])
+    } else {
+        None
+    }
+}
+
+/// Catch voice lines that room texts, triggers and dialogue ask for but don't
+/// exist.
+pub fn check_voice_lines(app: &App) -> io::Result<()> {
+    let in_texts = app.rooms.values().flat_map(|room| {
+        let definition = room.definition();
+        definition
+            .enter_text
+            .lines()
+            .chain(definition.enter_first_text.lines())
+            .filter_map(voice_line_id)
+            .collect::<Vec<&str>>()
+    });
+    let in_dialogue = app
+        .npcs
+        .values()
+        .flat_map(|definition| definition.tree.effects());
+    let in_effects = app
+        .triggers
+        .iter()
+        .flat_map(|trigger| &trigger.effects)
+        .chain(in_dialogue)
+        .filter_map(|effect| match effect {
+            Effect::AiSays(id) => Some(id.as_str()),
+            _ => None,
+        });
+    for id in in_texts.chain(in_effects) {
+        if !app.ai.lines.contains_key(id) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("unknown voice line {}", id),
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Split after every sentence, keeping the punctuation.
+fn sentences(line: &str) -> Vec<&str> {
+    let mut sentences = vec![];
+    let mut start = 0;
+    for (index, c) in line.char_indices() {
+        if (c == '.' || c == '!' || c == '?') && line[index + 1..].starts_with(' ') {
+            sentences.push(&line[start..=index]);
+            start = index + 2;
+        }
+    }
+    sentences.push(&line[start..]);
+    sentences
+}
+
+fn corrupt_word(word: &str, damage: f64, rng: &mut StdRng) -> String {
+    let mut corrupted: String = word
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() && rng.gen_bool(damage * damage * 0.15) {
+                GLITCHES[rng.gen_range(0, GLITCHES.len())]
+            } else {
+                c
+            }
+        })
+        .collect();
+    // Hang on the first letters, like "re-re-rebooting".
+    if word.chars().take_while(|c| c.is_alphabetic()).count() > 2 && rng.gen_bool(damage * 0.2) {
+        let start: String = corrupted.chars().take(rng.gen_range(1, 3)).collect();
+        corrupted = format!("{}{}", (start + "-").repeat(rng.gen_range(1, 3)), corrupted);
+    }
+    corrupted
+}
+
+/// Garble a voice line the more, the less integrity the AI has left. Words
+/// stutter, letters get swapped for noise and sentences after the first one
+/// break off halfway.
+pub fn corrupt(line: &str, integrity: i32, rng: &mut StdRng) -> String {
+    let damage = f64::from(FULL_INTEGRITY - integrity.max(0).min(FULL_INTEGRITY))
+        / f64::from(FULL_INTEGRITY);
+    if damage == 0.0 {
+        return line.to_string();
+    }
+
+    let mut corrupted = vec![];
+    for (index, sentence) in sentences(line).into_iter().enumerate() {
+        let words: Vec<String> = sentence
+            .split(' ')
+            .map(|word| corrupt_word(word, damage, rng))
+            .collect();
+        if index > 0 && words.len() > 2 && rng.gen_bool(damage * 0.3) {
+            let end = rng.gen_range(1, words.len());
+            corrupted.push(words[..end].join(" ") + "-");
+        } else {
+            corrupted.push(words.join(" "));
+        }
+    }
+    corrupted.join(" ")
+}
+
+/// Have the AI speak one of its lines, as well as it still can.
+pub fn say(app: &mut App, id: &str) {
+    let (message, game_event_type) = match app.ai.lines.get(id) {
+        Some(line) => (
+            format!("\"{}\"", corrupt(line, app.state.ai_integrity, &mut app.state.rng)),
+            GameEventType::Ai,
+        ),
+        None => (format!("Unknown voice line {}", id), GameEventType::Debug),
+    };
+    app.event_queue
+        .schedule_action(Action::Message(message, game_event_type));
+}
+
+/// Lower the AI's integrity, it can't drop below 0.
+pub fn damage_ai(app: &mut App, amount: i32) {
+    app.state.ai_integrity = (app.state.ai_integrity - amount).max(0);
+}
+
+/// Log a room text, letting the AI say every `{ai:<id>}` line in it.
+pub fn narrate(app: &mut App, text: &str) {
+    let mut narration: Vec<&str> = vec![];
+    for line in text.lines() {
+        match voice_line_id(line) {
+            Some(id) => {
+                if !narration.is_empty() {
+                    app.event_queue.schedule_action(Action::Message(
+                        narration.join("\n"),
+                        GameEventType::Normal,
+                    ));
+                    narration.clear();
+                }
+                say(app, id);
+            }
+            None => narration.push(line),
+        }
+    }
+    if !narration.is_empty() {
+        // Keep the blank line a text file ends with.
+        if text.ends_with('\n') {
+            narration.push("");
+        }
+        app.event_queue.schedule_action(Action::Message(
+            narration.join("\n"),
+            GameEventType::Normal,
+        ));
+    }
+}
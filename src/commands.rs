@@ -1,7 +1,16 @@
-use crate::action::Action;
+use crate::action::{Action, Target};
 use crate::game_event::GameEventType;
-use crate::room::{adjacent_rooms, room_type_from_name};
-use crate::state::State;
+use crate::fixture::fixture_from_name;
+use crate::help::{help, hint};
+use crate::entities::{item_from_name, item_name, npc_from_name, npc_name, npcs_in_room, Item};
+use crate::door::{exit_towards, exits, Direction, Door};
+use crate::inventory::is_in_room;
+use crate::room::{room_name, room_type_from_name};
+use crate::save::slots;
+use crate::utils::edit_distance;
+use crate::App;
+use std::fmt;
+use std::iter;
 use pest_derive::Parser;
 use pest::Parser;
 
@@ -9,74 +18,588 @@ use pest::Parser;
 #[grammar = "command.pest"]
 struct CommandParser;
 
-static HELP_TEXT: &'static str =
-    "Use one of the following commands: enter, attack, dodge, pickup, use.";
+pub static HELP_TEXT: &'static str =
+    "That's not a command I know. Type \"help\" to list them all.";
 
-pub fn try_handle_command(tokens: String, state: &State) -> Vec<Action> {
-    let mut parse = match CommandParser::parse(Rule::command, &tokens) {
-        Ok(parse) => parse.flatten(),
-        Err(_) => {
-            return vec![Action::Message(HELP_TEXT.into(), GameEventType::Failure)];
+/// A command broken into its parts. The verb is always the canonical one, so
+/// "go", "walk" and "enter" all come out as "enter".
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParsedCommand {
+    pub verb: String,
+    pub direct_object: Option<String>,
+    pub preposition: Option<String>,
+    pub indirect_object: Option<String>,
+}
+
+impl fmt::Display for ParsedCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.verb)?;
+        for part in &[&self.direct_object, &self.preposition, &self.indirect_object] {
+            if let Some(part) = part {
+                write!(f, " {}", part)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Every verb `try_handle_command` knows, along with the words meaning the same.
+static SYNONYMS: &'static [(&'static str, &'static [&'static str])] = &[
+    ("enter", &["enter", "go", "walk", "move"]),
+    ("look", &["look", "l"]),
+    ("attack", &["attack", "hit", "strike", "fight"]),
+    ("dodge", &["dodge", "duck", "evade"]),
+    ("take", &["take", "get", "grab", "pickup", "pick up"]),
+    ("drop", &["drop", "discard"]),
+    ("examine", &["examine", "inspect", "x"]),
+    ("inventory", &["inventory", "inv", "i"]),
+    ("memories", &["memories", "remember", "recall"]),
+    ("talk", &["talk", "speak", "chat"]),
+    ("use", &["use", "apply"]),
+    ("save", &["save"]),
+    ("load", &["load", "restore"]),
+    ("help", &["help", "commands"]),
+    ("hint", &["hint"]),
+];
+
+// The words meaning `verb`, itself first.
+fn synonyms(verb: &str) -> &'static [&'static str] {
+    SYNONYMS
+        .iter()
+        .find(|(canonical, _)| *canonical == verb)
+        .map_or(&[], |(_, words)| words)
+}
+
+fn canonical_verb(word: &str) -> String {
+    SYNONYMS
+        .iter()
+        .find(|(_, words)| words.contains(&word))
+        .map_or(word, |(verb, _)| verb)
+        .to_string()
+}
+
+// Collapses runs of spaces, so "slush   lobby" still names the lobby.
+fn words(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Break a line of input into a `ParsedCommand`, or `None` if it is no command.
+pub fn parse_command(line: &str) -> Option<ParsedCommand> {
+    let command = CommandParser::parse(Rule::command, line).ok()?;
+    let mut parsed = ParsedCommand::default();
+    for pair in command.flatten() {
+        match pair.as_rule() {
+            Rule::verb => parsed.verb = canonical_verb(&words(pair.as_str()).to_lowercase()),
+            Rule::direct_object => parsed.direct_object = Some(words(pair.into_inner().as_str())),
+            Rule::preposition => parsed.preposition = Some(pair.as_str().to_lowercase()),
+            Rule::indirect_object => {
+                parsed.indirect_object = Some(words(pair.into_inner().as_str()))
+            }
+            _ => {}
+        }
+    }
+    Some(parsed)
+}
+
+// Things that can be used without carrying them.
+static FIXTURES: &'static [&'static str] = &["terminal", "casket", "console", "door"];
+
+// What could follow `verb` right now.
+fn objects(app: &App, verb: &str) -> Vec<String> {
+    let item_names = |items: Vec<Item>| -> Vec<String> {
+        items
+            .into_iter()
+            .map(|item| item_name(&app.items, item).to_string())
+            .collect()
+    };
+    let npc_names: Vec<String> = npcs_in_room(app)
+        .into_iter()
+        .map(|npc| npc_name(app, npc).to_string())
+        .collect();
+    let carried = app.state.carried_items();
+    let lying_around: Vec<Item> = app
+        .items
+        .keys()
+        .cloned()
+        .filter(|item| !app.state.has_item(*item) && is_in_room(app, *item))
+        .collect();
+
+    match verb {
+        "enter" => exits(&app.doors, app.state.current_room)
+            .into_iter()
+            .map(|room_type| room_name(&app.rooms, room_type).to_string())
+            .collect(),
+        "take" => item_names(lying_around),
+        "drop" => item_names(carried),
+        "examine" => item_names(carried.into_iter().chain(lying_around).collect())
+            .into_iter()
+            .chain(
+                app.rooms[&app.state.current_room]
+                    .definition()
+                    .fixtures
+                    .iter()
+                    .map(|fixture| fixture.name.clone()),
+            )
+            .chain(npc_names.clone())
+            .collect(),
+        "use" => item_names(carried)
+            .into_iter()
+            .chain(FIXTURES.iter().map(|fixture| fixture.to_string()))
+            .collect(),
+        "talk" => npc_names,
+        "save" | "load" => slots(),
+        "help" => SYNONYMS.iter().map(|(verb, _)| verb.to_string()).collect(),
+        _ => vec![],
+    }
+}
+
+// What could follow "on" or "with" after `verb` right now.
+fn indirect_objects(app: &App, verb: &str) -> Vec<String> {
+    let current_room = app.state.current_room;
+    let carried = app.state.carried_items().into_iter();
+    match verb {
+        "use" => {
+            let doors = exits(&app.doors, current_room)
+                .into_iter()
+                .map(|room_type| room_name(&app.rooms, room_type).to_string());
+            let enemy = app.rooms[&current_room]
+                .definition()
+                .enemy
+                .clone()
+                .filter(|_| app.state.get_current_enemy(current_room).is_some());
+            let npcs = npcs_in_room(app)
+                .into_iter()
+                .map(|npc| npc_name(app, npc).to_string());
+            let items = carried
+                .chain(app.items.keys().cloned().filter(|item| is_in_room(app, *item)))
+                .map(|item| item_name(&app.items, item).to_string());
+            doors
+                .chain(enemy)
+                .chain(npcs)
+                .chain(FIXTURES.iter().map(|fixture| fixture.to_string()))
+                .chain(items)
+                .collect()
+        }
+        "attack" => carried.map(|item| item_name(&app.items, item).to_string()).collect(),
+        _ => vec![],
+    }
+}
+
+/// Full lines that `line` could be completed to, for tab completion.
+pub fn completions(app: &App, line: &str) -> Vec<String> {
+    let end = match line.find(' ') {
+        Some(end) => end,
+        None => {
+            let line = line.to_lowercase();
+            return SYNONYMS
+                .iter()
+                .flat_map(|(_, words)| words.iter())
+                .filter(|word| !word.contains(' ') && word.starts_with(&line))
+                .map(|word| word.to_string())
+                .collect();
+        }
+    };
+    let verb = canonical_verb(&line[..end].to_lowercase());
+    // Past "on" or "with" it is the second object being typed.
+    let second = [" on ", " with "]
+        .iter()
+        .filter_map(|preposition| {
+            line.to_ascii_lowercase()
+                .rfind(preposition)
+                .map(|index| index + preposition.len())
+        })
+        .max();
+    let (start, candidates) = match second {
+        Some(start) => (start, indirect_objects(app, &verb)),
+        None => (end + 1, objects(app, &verb)),
+    };
+    let (typed, partial) = (&line[..start], line[start..].to_ascii_lowercase());
+    candidates
+        .into_iter()
+        .filter(|name| name.to_lowercase().starts_with(&partial))
+        .map(|name| format!("{}{}", typed, name))
+        .collect()
+}
+
+// Commands that act on a single item, like `drop crowbar`.
+fn item_action(app: &App, object: Option<&str>, verb: &str, action: fn(Item) -> Action) -> Vec<Action> {
+    match object {
+        Some(name) => match item_from_name(&app.items, name) {
+            Some(item) => vec![action(item)],
+            None => vec![Action::Message(
+                format!("There is no {} here.", name),
+                GameEventType::Failure,
+            )],
+        },
+        None => vec![Action::Message(
+            format!("Specify an item to {}!", verb),
+            GameEventType::Failure,
+        )],
+    }
+}
+
+fn talk(app: &App, name: Option<&str>) -> Vec<Action> {
+    let npcs = npcs_in_room(app);
+    let npc = match name {
+        Some(name) => npc_from_name(app, name),
+        // With only one person around it's clear who is meant.
+        None if npcs.len() == 1 => npcs.first().cloned(),
+        None => {
+            return vec![Action::Message(
+                String::from("Specify who to talk to!"),
+                GameEventType::Failure,
+            )]
         }
     };
-    let object = parse.clone().find(|pair| pair.as_rule() == Rule::object)
-        .map(|v| v.as_str());
-    let verb = parse.find(|pair| pair.as_rule() == Rule::verb);
-
-    match verb.map(|v| v.as_str()) {
-        Some("enter") => {
-            if let Some(room_name) = object {
-                let available_rooms = adjacent_rooms(state.current_room);
-                let maybe_room_type = room_type_from_name(&room_name);
-                match maybe_room_type {
-                    Some(room_type) => {
-                        if available_rooms.contains(&room_type) || cfg!(debug_assertions) {
-                            vec![Action::Leave(state.current_room), Action::Enter(room_type)]
-                        } else {
-                            vec![Action::Message(
-                                String::from("You can't go to ") + &room_name + " from here.",
-                                GameEventType::Failure,
-                            )]
-                        }
-                    }
-                    None => vec![Action::Message(
-                        String::from("There is no room named \"") + &room_name + "\".",
-                        GameEventType::Failure,
-                    )],
-                }
+    match npc {
+        Some(npc) => vec![Action::Talk(npc)],
+        None => vec![Action::Message(
+            format!("There is no {} here to talk to.", name.unwrap_or_default()),
+            GameEventType::Failure,
+        )],
+    }
+}
+
+// Items nearby come first, then the room's fixtures and characters, then
+// items elsewhere so the handler can say they aren't here.
+fn examine(app: &App, object: Option<&str>) -> Vec<Action> {
+    if let Some(name) = object {
+        let item = item_from_name(&app.items, name);
+        let nearby = item.map_or(false, |item| app.state.has_item(item) || is_in_room(app, item));
+        if !nearby {
+            if let Some(fixture) = fixture_from_name(app, name) {
+                return vec![Action::ExamineFixture(fixture.name.clone())];
+            }
+            match npc_from_name(app, name) {
+                Some(npc) if npcs_in_room(app).contains(&npc) => return vec![Action::ExamineNpc(npc)],
+                _ => {}
+            }
+        }
+    }
+    item_action(app, object, "examine", Action::Examine)
+}
+
+// What "use X on <name>" refers to: a door, the enemy, a character, a fixture
+// or an item.
+fn target_from_name(app: &App, item: Item, name: &str) -> Option<Target> {
+    let name = name.to_lowercase();
+    let current_room = app.state.current_room;
+    let doors: Vec<&Door> = app
+        .doors
+        .iter()
+        .filter(|door| door.is_in(current_room))
+        .collect();
+
+    // A lone "door" means the one the item fits, or the only one there is.
+    if name == "door" {
+        let door = doors
+            .iter()
+            .find(|door| door.definition.key == Some(item))
+            .or_else(|| if doors.len() == 1 { doors.first() } else { None });
+        return door.map(|door| Target::Door(door.far_side(current_room)));
+    }
+    match name.as_str() {
+        "terminal" => return Some(Target::Terminal),
+        "casket" => return Some(Target::Casket),
+        "console" => return Some(Target::Console),
+        _ => {}
+    }
+
+    let enemy = app.rooms[&current_room].definition().enemy.as_ref();
+    if enemy == Some(&name) && app.state.get_current_enemy(current_room).is_some() {
+        return Some(Target::Enemy);
+    }
+    if let Some(npc) = npc_from_name(app, &name).filter(|npc| npcs_in_room(app).contains(npc)) {
+        return Some(Target::Npc(npc));
+    }
+
+    // "cryocontrol door" and "vent" both name a door by the room behind it.
+    let room = name.trim_end_matches(" door");
+    if let Some(room_type) = room_type_from_name(&app.rooms, room) {
+        if doors.iter().any(|door| door.far_side(current_room) == room_type) {
+            return Some(Target::Door(room_type));
+        }
+    }
+
+    item_from_name(&app.items, &name)
+        .filter(|other| app.state.has_item(*other) || is_in_room(app, *other))
+        .map(Target::Item)
+}
+
+fn use_object(app: &App, object: Option<&str>) -> Vec<Action> {
+    let lowercase = object.map(str::to_lowercase);
+    match lowercase.as_ref().map(String::as_str) {
+        Some("terminal") => vec![Action::UseTerminal],
+        Some("casket") => vec![Action::UseCasket],
+        Some("console") => vec![Action::UseConsole],
+        Some("door") => vec![Action::UseDoor],
+        _ => match object.and_then(|name| item_from_name(&app.items, name)) {
+            Some(item) => vec![Action::UseItem(item)],
+            None => vec![Action::Message(
+                String::from("No such item."),
+                GameEventType::Failure,
+            )],
+        },
+    }
+}
+
+fn use_on(app: &App, item_name: &str, target_name: &str) -> Vec<Action> {
+    let item = match item_from_name(&app.items, item_name) {
+        Some(item) => item,
+        None => {
+            return vec![Action::Message(
+                String::from("No such item."),
+                GameEventType::Failure,
+            )]
+        }
+    };
+    match target_from_name(app, item, target_name) {
+        Some(target) => vec![Action::UseOn(item, target)],
+        None if target_name.eq_ignore_ascii_case("door") => vec![Action::Message(
+            String::from("Which door? Name the room it leads to."),
+            GameEventType::Failure,
+        )],
+        None => vec![Action::Message(
+            format!("There is no {} here.", target_name),
+            GameEventType::Failure,
+        )],
+    }
+}
+
+// Fighting whatever guards the room, as in "attack the rat with the crowbar".
+// Fighting with an item is using it on the enemy.
+fn attack(app: &App, object: Option<&str>, instrument: Option<&str>) -> Vec<Action> {
+    let current_room = app.state.current_room;
+    let enemy_here = app.state.get_current_enemy(current_room).is_some();
+    if let Some(name) = object {
+        let enemy = app.rooms[&current_room].definition().enemy.as_ref();
+        if !enemy_here || enemy.map_or(true, |enemy| !enemy.eq_ignore_ascii_case(name)) {
+            return vec![Action::Message(
+                format!("There is no {} here to fight.", name),
+                GameEventType::Failure,
+            )];
+        }
+    }
+    match instrument {
+        // The attack handler knows what to say when nothing is there.
+        Some(_) if !enemy_here => vec![Action::Attack],
+        Some(name) => match item_from_name(&app.items, name).filter(|item| app.state.has_item(*item)) {
+            Some(item) => vec![Action::UseOn(item, Target::Enemy)],
+            None => vec![Action::Message(
+                format!("You don't have a {}.", name),
+                GameEventType::Failure,
+            )],
+        },
+        None => vec![Action::Attack],
+    }
+}
+
+// Walking through a door, given a room name or a direction like "north".
+fn enter(app: &App, target: &str) -> Vec<Action> {
+    let current_room = app.state.current_room;
+    if let Some(direction) = Direction::from_word(&target.to_lowercase()) {
+        return match exit_towards(&app.doors, &app.rooms, current_room, direction) {
+            Some(room_type) => vec![Action::Leave(current_room), Action::Enter(room_type)],
+            None => vec![Action::Message(
+                format!("There is no way {} from here.", target.to_lowercase()),
+                GameEventType::Failure,
+            )],
+        };
+    }
+
+    match room_type_from_name(&app.rooms, target) {
+        Some(room_type) => {
+            if exits(&app.doors, current_room).contains(&room_type) || cfg!(debug_assertions) {
+                vec![Action::Leave(current_room), Action::Enter(room_type)]
             } else {
                 vec![Action::Message(
-                    String::from("Specify a room to enter!"),
+                    format!("You can't go to {} from here.", target),
                     GameEventType::Failure,
                 )]
             }
         }
-        Some("look") => vec![Action::ShowEnterText],
-        Some("attack") => vec![Action::Attack],
-        Some("dodge") => vec![Action::Dodge],
-        Some("pickup") => {
-            match object {
-                Some("crowbar") => vec![Action::PickUpCrowbar],
-                Some("keycard") => vec![Action::PickUpKeycard],
-                _ => vec![Action::Message(
-                    String::from("This item doesn't exist."),
-                    GameEventType::Failure,
-                )],
-            }
+        None => vec![Action::Message(
+            format!("There is no room named \"{}\".", target),
+            GameEventType::Failure,
+        )],
+    }
+}
+
+static DIRECTIONS: &'static [&'static str] = &["north", "south", "east", "west"];
+
+// Every name an object of `verb` could have, whether it is nearby or not.
+fn known_names(app: &App, verb: &str) -> Vec<String> {
+    let rooms = app.rooms.values().flat_map(|room| {
+        let definition = room.definition();
+        iter::once(definition.name.clone()).chain(definition.aliases.clone())
+    });
+    let items = app.items.values().flat_map(|definition| {
+        iter::once(definition.name.clone()).chain(definition.aliases.clone())
+    });
+    let enemies = app
+        .rooms
+        .values()
+        .filter_map(|room| room.definition().enemy.clone());
+    let fixtures = app.rooms.values().flat_map(|room| {
+        room.definition()
+            .fixtures
+            .iter()
+            .flat_map(|fixture| iter::once(fixture.name.clone()).chain(fixture.aliases.clone()))
+            .collect::<Vec<String>>()
+    });
+    let npcs = app.npcs.values().flat_map(|definition| {
+        iter::once(definition.name.clone()).chain(definition.aliases.clone())
+    });
+
+    match verb {
+        "enter" => rooms
+            .chain(DIRECTIONS.iter().map(|direction| direction.to_string()))
+            .collect(),
+        "take" | "drop" => items.collect(),
+        "examine" | "look" => items.chain(fixtures).chain(npcs).collect(),
+        "talk" => npcs.collect(),
+        // A door goes by the room behind it, with or without " door".
+        "use" => items
+            .chain(rooms.flat_map(|room| vec![format!("{} door", room), room]))
+            .chain(enemies)
+            .chain(npcs)
+            .chain(FIXTURES.iter().map(|fixture| fixture.to_string()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+// The candidate `word` is most likely a typo of. Nothing if none is close
+// enough, or if two are equally close.
+fn closest<'a>(word: &str, candidates: &'a [String]) -> Option<&'a str> {
+    let word = word.to_lowercase();
+    let max_typos = (word.chars().count() / 3).max(1);
+    let mut matches: Vec<(usize, &str)> = candidates
+        .iter()
+        .map(|candidate| (edit_distance(&word, &candidate.to_lowercase()), candidate.as_str()))
+        .filter(|(distance, _)| *distance <= max_typos)
+        .collect();
+    matches.sort();
+    matches.dedup();
+
+    let (distance, candidate) = *matches.first()?;
+    match matches.get(1) {
+        Some((next, _)) if *next == distance => None,
+        _ => Some(candidate),
+    }
+}
+
+fn corrected_name(names: &[String], name: &str) -> Option<String> {
+    if names.iter().any(|known| known.eq_ignore_ascii_case(name)) {
+        return None;
+    }
+    closest(name, names).map(String::from)
+}
+
+/// `command` with its typos fixed, or `None` if there is nothing to fix.
+pub fn correct(app: &App, command: &ParsedCommand) -> Option<ParsedCommand> {
+    let mut corrected = command.clone();
+    let verbs: Vec<String> = SYNONYMS
+        .iter()
+        .flat_map(|(_, words)| words.iter())
+        .map(|word| word.to_string())
+        .collect();
+    if !verbs.contains(&command.verb) {
+        corrected.verb = canonical_verb(closest(&command.verb, &verbs)?);
+    }
+
+    let names = known_names(app, &corrected.verb);
+    for object in &mut [&mut corrected.direct_object, &mut corrected.indirect_object] {
+        let fixed = object.as_ref().and_then(|name| corrected_name(&names, name));
+        if fixed.is_some() {
+            **object = fixed;
         }
-        Some("use") => {
-            match object {
-                Some("crowbar") => vec![Action::UseCrowbar],
-                Some("terminal") => vec![Action::UseTerminal],
-                Some("keycard") => vec![Action::UseKeycard],
-                Some("casket") => vec![Action::UseCasket],
-                Some("door") => vec![Action::UseDoor],
-                _ => vec![Action::Message(
-                    String::from("No such item."),
-                    GameEventType::Failure,
-                )],
-            }
+    }
+
+    if corrected == *command {
+        None
+    } else {
+        Some(corrected)
+    }
+}
+
+pub fn try_handle_command(tokens: String, app: &App) -> Vec<Action> {
+    // While talking, a number picks an answer.
+    if app.state.conversation.is_some() {
+        if let Ok(number) = tokens.trim().parse() {
+            return vec![Action::Choose(number)];
+        }
+    }
+    let command = match parse_command(&tokens) {
+        Some(command) => command,
+        None => return vec![Action::Message(HELP_TEXT.into(), GameEventType::Failure)],
+    };
+    match correct(app, &command) {
+        Some(corrected) if app.autocorrect => {
+            let mut actions = vec![Action::Message(
+                format!("(Assuming you meant \"{}\".)", corrected),
+                GameEventType::Normal,
+            )];
+            actions.extend(run_command(app, &corrected));
+            actions
+        }
+        Some(corrected) => vec![Action::Message(
+            format!("Did you mean \"{}\"?", corrected),
+            GameEventType::Failure,
+        )],
+        None => run_command(app, &command),
+    }
+}
+
+fn run_command(app: &App, command: &ParsedCommand) -> Vec<Action> {
+    let object = command.direct_object.as_ref().map(String::as_str);
+    // The second object, as in "look at the photo" or "go to the lobby".
+    let indirect_object = command.indirect_object.as_ref().map(String::as_str);
+
+    match command.verb.as_str() {
+        "enter" => match object.or(indirect_object) {
+            Some(target) => enter(app, target),
+            None => vec![Action::Message(
+                String::from("Specify a room to enter!"),
+                GameEventType::Failure,
+            )],
+        },
+        "look" => match object.or(indirect_object) {
+            Some(name) => examine(app, Some(name)),
+            None => vec![Action::ShowEnterText],
+        },
+        "attack" => attack(app, object, indirect_object),
+        "dodge" => vec![Action::Dodge],
+        "take" => item_action(app, object, "take", Action::PickUp),
+        "drop" => item_action(app, object, "drop", Action::Drop),
+        "examine" => examine(app, object),
+        "inventory" => vec![Action::ShowInventory],
+        "memories" => vec![Action::ShowMemories],
+        "talk" => talk(app, object.or(indirect_object)),
+        "use" => match (object, indirect_object) {
+            (Some(item), Some(target)) => use_on(app, item, target),
+            _ => use_object(app, object),
+        },
+        "save" => match object {
+            Some(slot) => vec![Action::Save(slot.into())],
+            None => vec![Action::Message(
+                String::from("Specify a slot to save to!"),
+                GameEventType::Failure,
+            )],
+        },
+        "load" => match object {
+            Some(slot) => vec![Action::Load(slot.into())],
+            None => vec![Action::Message(
+                String::from("Specify a slot to load!"),
+                GameEventType::Failure,
+            )],
+        },
+        "help" => {
+            let verb = object.map(canonical_verb);
+            let synonyms = verb.as_ref().map_or(&[][..], |verb| synonyms(verb));
+            help(app, verb.as_ref().map(String::as_str), synonyms)
         }
+        "hint" => vec![hint(app)],
         _ => vec![Action::Message(HELP_TEXT.into(), GameEventType::Failure)],
     }
 }
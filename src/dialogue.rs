@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use tui::style::{Color, Style};
+use tui::widgets::Text;
+
+use crate::entities::{is_alive, npc_name, npcs_in_room, Npc};
+use crate::fixture::Description;
+use crate::game_event::GameEventType;
+use crate::trigger::{apply, holds, Condition, Effect};
+use crate::utils::message;
+use crate::App;
+
+// Every conversation opens here.
+static START_NODE: &'static str = "start";
+
+/// A character's dialogue tree, as written down in `assets/dialogue/`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Dialogue {
+    pub nodes: HashMap<String, Node>,
+}
+
+/// One thing the character says, and what the player can answer.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Node {
+    // Checked in order, the first one whose conditions hold is said.
+    pub says: Vec<Description>,
+    // Applied every time the node is reached, after picking what is said.
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    pub choices: Vec<Choice>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Choice {
+    pub text: String,
+    // Only offered while these hold.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    #[serde(default)]
+    pub effects: Vec<Effect>,
+    // Where the conversation goes next, it ends if left out.
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+impl Dialogue {
+    /// Make sure the tree can't lead nowhere.
+    pub fn check(&self) -> Result<(), String> {
+        if !self.nodes.contains_key(START_NODE) {
+            return Err(format!("no \"{}\" node", START_NODE));
+        }
+        for (id, node) in &self.nodes {
+            for choice in &node.choices {
+                match choice.next {
+                    Some(ref next) if !self.nodes.contains_key(next) => {
+                        return Err(format!("{} leads to unknown node {}", id, next));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every effect in the tree, on nodes and choices alike.
+    pub fn effects(&self) -> impl Iterator<Item = &Effect> {
+        self.nodes.values().flat_map(|node| {
+            node.effects
+                .iter()
+                .chain(node.choices.iter().flat_map(|choice| &choice.effects))
+        })
+    }
+}
+
+/// Who the player is talking to and where the conversation is at.
+#[derive(Debug, Clone)]
+pub struct Conversation {
+    pub npc: Npc,
+    pub node: String,
+    // Indices of the choices on offer, in the order they are numbered.
+    pub choices: Vec<usize>,
+}
+
+fn node<'a>(app: &'a App, npc: Npc, id: &str) -> &'a Node {
+    &app.npcs[&npc].tree.nodes[id]
+}
+
+// Say the node's line and offer the choices that currently apply.
+fn enter_node(app: &mut App, npc: Npc, id: String) {
+    // The line is picked before the effects, so a first meeting can mark
+    // itself as over without changing what is said.
+    let says = node(app, npc, &id)
+        .says
+        .iter()
+        .find(|says| says.conditions.iter().all(|condition| holds(app, condition)))
+        .map(|says| says.text.clone());
+    let effects = node(app, npc, &id).effects.clone();
+    for effect in effects {
+        apply(app, effect);
+    }
+
+    let node = node(app, npc, &id);
+    let choices: Vec<usize> = node
+        .choices
+        .iter()
+        .enumerate()
+        .filter(|(_, choice)| choice.conditions.iter().all(|condition| holds(app, condition)))
+        .map(|(index, _)| index)
+        .collect();
+    let listing = choices
+        .iter()
+        .enumerate()
+        .map(|(number, index)| format!("{}. {}", number + 1, node.choices[*index].text))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    if let Some(says) = says {
+        message(app, says, GameEventType::Normal);
+    }
+    // Nothing left to answer means there is nothing left to say.
+    let ends = choices.is_empty();
+    app.state.conversation = Some(Conversation {
+        npc,
+        node: id,
+        choices,
+    });
+    if ends {
+        end_conversation(app);
+    } else {
+        message(app, listing, GameEventType::Success);
+    }
+}
+
+pub fn talk(app: &mut App, npc: Npc) {
+    if !npcs_in_room(app).contains(&npc) {
+        let text = format!("The {} isn't here.", npc_name(app, npc));
+        return message(app, text, GameEventType::Failure);
+    }
+    if !is_alive(app, npc) {
+        let text = format!("The {} doesn't answer anymore.", npc_name(app, npc));
+        return message(app, text, GameEventType::Failure);
+    }
+    enter_node(app, npc, START_NODE.to_string());
+}
+
+/// Answer with the choice numbered `number`, counting from 1.
+pub fn choose(app: &mut App, number: usize) {
+    let conversation = match app.state.conversation.clone() {
+        Some(conversation) => conversation,
+        None => {
+            let text = String::from("You aren't talking to anyone.");
+            return message(app, text, GameEventType::Failure);
+        }
+    };
+    let index = match number.checked_sub(1).and_then(|number| conversation.choices.get(number)) {
+        Some(index) => *index,
+        None => {
+            let text = format!("Pick an answer from 1 to {}.", conversation.choices.len());
+            return message(app, text, GameEventType::Failure);
+        }
+    };
+
+    let choice = node(app, conversation.npc, &conversation.node).choices[index].clone();
+    message(app, format!("\"{}\"", choice.text), GameEventType::Normal);
+    for effect in choice.effects {
+        apply(app, effect);
+    }
+    match choice.next {
+        Some(next) => enter_node(app, conversation.npc, next),
+        None => end_conversation(app),
+    }
+}
+
+pub fn end_conversation(app: &mut App) {
+    if let Some(conversation) = app.state.conversation.take() {
+        let farewell = app.npcs[&conversation.npc].farewell.clone();
+        message(app, farewell, GameEventType::Normal);
+    }
+}
+
+/// The choices on offer, for the panel shown while talking.
+pub fn format_conversation(app: &App) -> Vec<Text> {
+    let conversation = match app.state.conversation {
+        Some(ref conversation) => conversation,
+        None => return vec![],
+    };
+    let node = node(app, conversation.npc, &conversation.node);
+    let mut lines: Vec<Text> = conversation
+        .choices
+        .iter()
+        .enumerate()
+        .map(|(number, index)| Text::raw(format!("{}. {}\n", number + 1, node.choices[*index].text)))
+        .collect();
+    lines.push(Text::styled(
+        "Type the number of your answer.",
+        Style::default().fg(Color::DarkGray),
+    ));
+    lines
+}
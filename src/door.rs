@@ -0,0 +1,252 @@
+use std::io;
+use std::path::Path;
+
+use crate::entities::{item_name, Item};
+use crate::game_event::GameEventType;
+use crate::room::{room_name, MapShape, RoomType, Rooms};
+use crate::trigger::{holds, Condition};
+use crate::utils::{invalid_data, load_ron};
+use crate::{Action, App};
+
+/// A door as written down in `assets/doors.ron`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DoorDefinition {
+    pub from: RoomType,
+    pub to: RoomType,
+    // One-way doors can only be walked through from `from` to `to`.
+    #[serde(default)]
+    pub one_way: bool,
+    #[serde(default)]
+    pub locked: bool,
+    // Using this item next to the door unlocks it.
+    #[serde(default)]
+    pub key: Option<Item>,
+    // Shown next to the door label when entering a room.
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub unlock_message: Option<String>,
+    #[serde(default)]
+    pub closed_message: Option<String>,
+    // The door stays shut until these all hold, e.g. while an enemy guards it.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    #[serde(default = "default_sound")]
+    pub sound: bool,
+    // Passages drawn on the map between the two rooms.
+    #[serde(default)]
+    pub map: Vec<MapShape>,
+}
+
+fn default_sound() -> bool {
+    true
+}
+
+/// A door between two rooms, along with whether it is currently locked.
+#[derive(Debug)]
+pub struct Door {
+    pub definition: DoorDefinition,
+    pub locked: bool,
+}
+
+pub type Doors = Vec<Door>;
+
+impl Door {
+    pub fn new(definition: DoorDefinition) -> Door {
+        Door {
+            locked: definition.locked,
+            definition,
+        }
+    }
+
+    /// The room this door leads to when walked through from `room_type`.
+    pub fn leads_to(&self, room_type: RoomType) -> Option<RoomType> {
+        if self.definition.from == room_type {
+            Some(self.definition.to)
+        } else if self.definition.to == room_type && !self.definition.one_way {
+            Some(self.definition.from)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the door is in a wall of `room_type`, whichever way it opens.
+    pub fn is_in(&self, room_type: RoomType) -> bool {
+        self.definition.from == room_type || self.definition.to == room_type
+    }
+
+    /// The room on the other side when standing in `room_type`, which is how
+    /// doors are named.
+    pub fn far_side(&self, room_type: RoomType) -> RoomType {
+        if self.definition.from == room_type {
+            self.definition.to
+        } else {
+            self.definition.from
+        }
+    }
+}
+
+/// Read the door list and check that it only joins rooms that exist.
+pub fn load_doors(path: &Path, rooms: &Rooms) -> io::Result<Doors> {
+    let definitions: Vec<DoorDefinition> = load_ron(path)?;
+    for definition in &definitions {
+        for room_type in &[definition.from, definition.to] {
+            if !rooms.contains_key(room_type) {
+                return Err(invalid_data(path, format!("door to unknown room {}", room_type)));
+            }
+        }
+    }
+    Ok(definitions.into_iter().map(Door::new).collect())
+}
+
+/// Every room that can be walked to from `room_type`.
+pub fn exits(doors: &Doors, room_type: RoomType) -> Vec<RoomType> {
+    doors
+        .iter()
+        .filter_map(|door| door.leads_to(room_type))
+        .collect()
+}
+
+/// A compass direction on the map, with north at the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub fn from_word(word: &str) -> Option<Direction> {
+        match word {
+            "north" | "n" => Some(Direction::North),
+            "south" | "s" => Some(Direction::South),
+            "east" | "e" => Some(Direction::East),
+            "west" | "w" => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+/// The exit from `from` that lies in `direction` on the map, if any.
+pub fn exit_towards(doors: &Doors, rooms: &Rooms, from: RoomType, direction: Direction) -> Option<RoomType> {
+    // Twice the center, which keeps it in whole numbers.
+    let center = |room_type: RoomType| {
+        let map = &rooms[&room_type].definition().map;
+        (
+            2 * map.x as i32 + map.width as i32,
+            2 * map.y as i32 + map.height as i32,
+        )
+    };
+    let (x, y) = center(from);
+    exits(doors, from).into_iter().find(|to| {
+        let (dx, dy) = (center(*to).0 - x, center(*to).1 - y);
+        let towards = if dy.abs() >= dx.abs() {
+            if dy > 0 {
+                Direction::North
+            } else {
+                Direction::South
+            }
+        } else if dx > 0 {
+            Direction::East
+        } else {
+            Direction::West
+        };
+        towards == direction
+    })
+}
+
+/// The door that leads from `from` to `to`, if there is one.
+pub fn door_between(doors: &Doors, from: RoomType, to: RoomType) -> Option<&Door> {
+    doors.iter().find(|door| door.leads_to(from) == Some(to))
+}
+
+/// Whether the door's conditions keep it shut right now, locked or not.
+pub fn is_blocked(app: &App, door: &Door) -> bool {
+    !door.definition.conditions.iter().all(|condition| holds(app, condition))
+}
+
+pub fn closed_message(door: &Door, rooms: &Rooms, to: RoomType) -> String {
+    match door.definition.closed_message {
+        Some(ref message) => message.clone(),
+        None => format!(
+            "The door to {} is closed and won't open.",
+            room_name(rooms, to)
+        ),
+    }
+}
+
+// Open the door at `index` in `app.doors` with its key.
+fn open_door(app: &mut App, index: usize) {
+    let door = &mut app.doors[index];
+    let name = room_name(&app.rooms, door.far_side(app.state.current_room));
+    let message = if door.locked {
+        Action::Message(
+            door.definition
+                .unlock_message
+                .clone()
+                .unwrap_or_else(|| format!("You open the door to {}.", name)),
+            GameEventType::Success,
+        )
+    } else {
+        Action::Message(
+            format!("The way to {} is already open.", name),
+            GameEventType::Failure,
+        )
+    };
+    door.locked = false;
+    app.event_queue.schedule_action(message);
+}
+
+/// Unlock whatever doors of the current room open with `item`.
+pub fn use_item(app: &mut App, item: Item) {
+    let item_name = item_name(&app.items, item).to_string();
+    if !app.state.has_item(item) {
+        app.event_queue.schedule_action(Action::Message(
+            format!("You don't have a {}.", item_name),
+            GameEventType::Failure,
+        ));
+        return;
+    }
+
+    let current_room = app.state.current_room;
+    let keyholes: Vec<usize> = (0..app.doors.len())
+        .filter(|index| {
+            let door = &app.doors[*index];
+            door.is_in(current_room) && door.definition.key == Some(item)
+        })
+        .collect();
+    if keyholes.is_empty() {
+        app.event_queue.schedule_action(Action::Message(
+            format!("There is nothing to use the {} on here.", item_name),
+            GameEventType::Failure,
+        ));
+    }
+    for index in keyholes {
+        open_door(app, index);
+    }
+}
+
+/// Use `item` on the door of the current room that leads to `to`.
+pub fn use_item_on_door(app: &mut App, item: Item, to: RoomType) {
+    let current_room = app.state.current_room;
+    let index = app
+        .doors
+        .iter()
+        .position(|door| door.is_in(current_room) && door.far_side(current_room) == to);
+    match index {
+        Some(index) if app.doors[index].definition.key == Some(item) => open_door(app, index),
+        Some(_) => app.event_queue.schedule_action(Action::Message(
+            format!(
+                "The {} doesn't open the door to {}.",
+                item_name(&app.items, item),
+                room_name(&app.rooms, to)
+            ),
+            GameEventType::Failure,
+        )),
+        None => app.event_queue.schedule_action(Action::Message(
+            format!("There is no door to {} here.", room_name(&app.rooms, to)),
+            GameEventType::Failure,
+        )),
+    }
+}
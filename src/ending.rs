@@ -0,0 +1,59 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game_over::GameOverCause;
+use crate::trigger::{holds, Condition};
+use crate::utils::load_ron;
+use crate::App;
+
+// Endings reached in any run, kept across games for the title menu.
+static UNLOCKED_ENDINGS_FILE: &'static str = "unlocked_endings.json";
+
+/// An ending as written down in `assets/endings.ron`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Ending {
+    pub id: String,
+    pub title: String,
+    // The ways the game can end that lead here.
+    pub causes: Vec<GameOverCause>,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    // Text file in `assets/endings/`. Replaced by its contents on load.
+    pub epilogue: String,
+}
+
+/// Read the ending registry along with the epilogue texts.
+pub fn load_endings(dir: &Path) -> io::Result<Vec<Ending>> {
+    let path = dir.join("endings.ron");
+    let mut endings: Vec<Ending> = load_ron(&path)?;
+    for ending in &mut endings {
+        ending.epilogue = fs::read_to_string(dir.join("endings").join(&ending.epilogue))?;
+    }
+    Ok(endings)
+}
+
+/// The first ending, in registry order, that fits how the game ended.
+pub fn pick_ending(app: &App, cause: GameOverCause) -> Option<&Ending> {
+    app.endings.iter().find(|ending| {
+        ending.causes.contains(&cause)
+            && ending.conditions.iter().all(|condition| holds(app, condition))
+    })
+}
+
+/// Ids of every ending reached so far, in the order they were first reached.
+pub fn unlocked_endings() -> Vec<String> {
+    fs::read_to_string(UNLOCKED_ENDINGS_FILE)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn unlock_ending(id: &str) -> io::Result<()> {
+    let mut unlocked = unlocked_endings();
+    if unlocked.iter().any(|unlocked| unlocked == id) {
+        return Ok(());
+    }
+    unlocked.push(id.to_string());
+    fs::write(UNLOCKED_ENDINGS_FILE, serde_json::to_string_pretty(&unlocked)?)
+}
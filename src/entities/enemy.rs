@@ -1,10 +1,12 @@
 extern crate rand;
 
+use rand::rngs::StdRng;
 use rand::Rng;
 
 use std::fmt::Debug;
 
-use crate::room::RoomType;
+use crate::entities::status::StatusEffectType;
+use crate::room::Rooms;
 use crate::sound::{AudioEvent, Effect};
 use crate::state::State;
 use crate::timer::{Timer, TimerType};
@@ -23,17 +25,22 @@ pub trait Enemy: Debug {
 
     fn reduce_health(&mut self, amount: i32) -> bool;
 
+    fn set_health(&mut self, health: i32);
+
     fn get_attack_strength(&self) -> i32;
 
     fn get_initial_attack_timers(&self) -> Vec<Timer>;
 
     fn get_attack_timers(&self, delay: u64) -> Vec<Timer>;
 
-    fn get_attack_message(&self) -> String;
+    fn get_attack_message(&self, rng: &mut StdRng) -> String;
 
-    fn get_enemy_attack_message(&self) -> String;
+    fn get_enemy_attack_message(&self, rng: &mut StdRng) -> String;
 
     fn get_death_message(&self) -> String;
+
+    // What a successful attack leaves the player suffering from.
+    fn get_wound(&self) -> Option<(StatusEffectType, u64)>;
 }
 
 #[derive(Debug)]
@@ -45,6 +52,7 @@ pub struct GenericEnemy {
     attack_messages: Vec<String>,
     enemy_attack_messages: Vec<String>,
     death_message: Option<String>,
+    wound: Option<(StatusEffectType, u64)>,
 }
 
 impl GenericEnemy {
@@ -56,6 +64,7 @@ impl GenericEnemy {
         attack_messages: Vec<String>,
         enemy_attack_messages: Vec<String>,
         death_message: Option<String>,
+        wound: Option<(StatusEffectType, u64)>,
     ) -> Self {
         GenericEnemy {
             enemy_type,
@@ -64,7 +73,8 @@ impl GenericEnemy {
             timer_length,
             attack_messages,
             enemy_attack_messages,
-            death_message
+            death_message,
+            wound,
         }
     }
 }
@@ -87,20 +97,24 @@ impl Enemy for GenericEnemy {
         false
     }
 
+    fn set_health(&mut self, health: i32) {
+        self.health = health;
+    }
+
     fn get_attack_strength(&self) -> i32 {
         self.attack_strength
     }
 
-    fn get_attack_message(&self) -> String {
-        if let Some(message) = rand::thread_rng().choose(&self.attack_messages) {
+    fn get_attack_message(&self, rng: &mut StdRng) -> String {
+        if let Some(message) = rng.choose(&self.attack_messages) {
             message.to_string()
         } else {
             String::from(format!("You attack the {:?}.", self.enemy_type))
         }
     }
 
-    fn get_enemy_attack_message(&self) -> String {
-        if let Some(message) = rand::thread_rng().choose(&self.enemy_attack_messages) {
+    fn get_enemy_attack_message(&self, rng: &mut StdRng) -> String {
+        if let Some(message) = rng.choose(&self.enemy_attack_messages) {
             message.to_string()
         } else {
             String::from(format!("The {:?} attacks!", self.enemy_type))
@@ -141,48 +155,66 @@ impl Enemy for GenericEnemy {
             format!("The {:?} has been slain.\n", self.enemy_type)
         }
     }
+
+    fn get_wound(&self) -> Option<(StatusEffectType, u64)> {
+        self.wound
+    }
 }
 
-pub fn initialize_enemies(state: &mut State) {
-    let rat_attack_messages = vec!["You stomp on the rat.".into()];
-    let rat_enemy_attack_messages = vec![
-        "The rat gnaws on your leg.".into(),
-        "The rat runs around you in circles. You try to follow it, stumbling.".into(),
-    ];
-
-    let rat = GenericEnemy::new(
-        EnemyType::Rat,
-        10,
-        5,
-        8 * 1000,
-        rat_attack_messages,
-        rat_enemy_attack_messages,
-        Some("The rat is dead. It drops its keycard.".into()),
-    );
-    state.enemies.insert(RoomType::Corridor, Box::new(rat));
-
-    let roomba_attack_messages = vec![
-        "You tackle the roomba. It topples over.".into(),
-        "You smash in one of the roombas many visual sensors.".into(),
-        "You kick the roomba, leaving a dent.".into(),
-        "You rip out one of the roombas appendages. It produces a high-pitched beeping wail."
-            .into(),
-    ];
-    let roomba_enemy_attack_messages = vec![
-        "The roomba vacuums your arm. Some of the skin comes off.".into(),
-        "The roomba swings its broom and hits your head.".into(),
-    ];
-
-    let roomba = GenericEnemy::new(
-        EnemyType::Roomba,
-        40,
-        20,
-        3 * 1000,
-        roomba_attack_messages,
-        roomba_enemy_attack_messages,
-        None,
-    );
-    state
-        .enemies
-        .insert(RoomType::Cryocontrol, Box::new(roomba));
+// Builds a fresh enemy from the templates that room definitions refer to.
+pub fn create_enemy(name: &str) -> Option<Box<Enemy>> {
+    match name {
+        "rat" => {
+            let rat_attack_messages = vec!["You stomp on the rat.".into()];
+            let rat_enemy_attack_messages = vec![
+                "The rat gnaws on your leg.".into(),
+                "The rat runs around you in circles. You try to follow it, stumbling.".into(),
+            ];
+
+            Some(Box::new(GenericEnemy::new(
+                EnemyType::Rat,
+                10,
+                5,
+                8 * 1000,
+                rat_attack_messages,
+                rat_enemy_attack_messages,
+                Some("The rat is dead. It drops its keycard.".into()),
+                Some((StatusEffectType::Bleeding, 10 * 1000)),
+            )))
+        }
+        "roomba" => {
+            let roomba_attack_messages = vec![
+                "You tackle the roomba. It topples over.".into(),
+                "You smash in one of the roombas many visual sensors.".into(),
+                "You kick the roomba, leaving a dent.".into(),
+                "You rip out one of the roombas appendages. It produces a high-pitched beeping wail."
+                    .into(),
+            ];
+            let roomba_enemy_attack_messages = vec![
+                "The roomba vacuums your arm. Some of the skin comes off.".into(),
+                "The roomba swings its broom and hits your head.".into(),
+            ];
+
+            Some(Box::new(GenericEnemy::new(
+                EnemyType::Roomba,
+                40,
+                20,
+                3 * 1000,
+                roomba_attack_messages,
+                roomba_enemy_attack_messages,
+                None,
+                None,
+            )))
+        }
+        _ => None,
+    }
+}
+
+pub fn initialize_enemies(state: &mut State, rooms: &Rooms) {
+    for (room_type, room) in rooms {
+        let enemy = room.definition().enemy.as_ref().and_then(|name| create_enemy(name));
+        if let Some(enemy) = enemy {
+            state.enemies.insert(*room_type, enemy);
+        }
+    }
 }
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::room::RoomType;
+use crate::state::State;
+use crate::utils::{intern, invalid_data, load_ron};
+
+/// Identifies an item by its key in `assets/items.ron`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct Item(&'static str);
+
+impl Item {
+    pub fn id(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl<'a> From<&'a str> for Item {
+    fn from(id: &'a str) -> Self {
+        Item(intern(id))
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Item {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Item {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(Item::from(id.as_str()))
+    }
+}
+
+/// Where an item currently is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ItemLocation {
+    Room(RoomType),
+    Player,
+    Container(Item),
+    // Used up or otherwise gone from the game.
+    Nowhere,
+}
+
+/// An item as written down in `assets/items.ron`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub description: String,
+    // A few words for the inventory panel.
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub weight: u32,
+    // Where the item is when a new game starts.
+    pub location: ItemLocation,
+    // How much oxygen breathing from the item gives back.
+    #[serde(default)]
+    pub oxygen: Option<i32>,
+    // Whether other items can be inside this one.
+    #[serde(default)]
+    pub container: bool,
+    // File name of a script in `assets/scripts/` with hooks for this item.
+    #[serde(default)]
+    pub script: Option<String>,
+}
+
+pub type Items = HashMap<Item, ItemDefinition>;
+
+/// Read the item list and check that every item starts somewhere that exists.
+pub fn load_item_definitions(path: &Path, rooms: &[RoomType]) -> io::Result<Items> {
+    let items: Items = load_ron(path)?;
+    for (item, definition) in &items {
+        match definition.location {
+            ItemLocation::Room(room_type) if !rooms.contains(&room_type) => {
+                return Err(invalid_data(path, format!("{} is in unknown room {}", item, room_type)));
+            }
+            ItemLocation::Container(container)
+                if !items.get(&container).map_or(false, |definition| definition.container) =>
+            {
+                return Err(invalid_data(path, format!("{} is in {}, which is no container", item, container)));
+            }
+            _ => {}
+        }
+    }
+    Ok(items)
+}
+
+pub fn item_from_name(items: &Items, name: &str) -> Option<Item> {
+    items
+        .iter()
+        .find(|(_, definition)| {
+            definition.name.eq_ignore_ascii_case(name)
+                || definition.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name))
+        })
+        .map(|(item, _)| *item)
+}
+
+pub fn item_name(items: &Items, item: Item) -> &str {
+    items
+        .get(&item)
+        .map_or(item.id(), |definition| definition.name.as_str())
+}
+
+pub fn initialize_items(state: &mut State, items: &Items) {
+    state.item_locations = items
+        .iter()
+        .map(|(item, definition)| (*item, definition.location))
+        .collect();
+}
@@ -1,5 +1,11 @@
 pub mod enemy;
+pub mod item;
+pub mod npc;
 pub mod player;
+pub mod status;
 
 pub use self::enemy::*;
+pub use self::item::*;
+pub use self::npc::*;
 pub use self::player::*;
+pub use self::status::*;
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use crate::dialogue::Dialogue;
+use crate::entities::status::StatusEffectType;
+use crate::fixture::Description;
+use crate::game_event::GameEventType;
+use crate::room::RoomType;
+use crate::state::State;
+use crate::timer::{Timer, TimerType};
+use crate::trigger::holds;
+use crate::utils::{intern, invalid_data, load_ron, message};
+use crate::{Action, App};
+
+// Characters get better or worse once per interval.
+const NPC_INTERVAL: u64 = 10 * 1000;
+
+// Health lost per interval while bleeding.
+const BLEEDING_DAMAGE: i32 = 4;
+
+/// Identifies a character by its key in `assets/npcs.ron`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct Npc(&'static str);
+
+impl Npc {
+    pub fn id(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl<'a> From<&'a str> for Npc {
+    fn from(id: &'a str) -> Self {
+        Npc(intern(id))
+    }
+}
+
+impl fmt::Display for Npc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Npc {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Npc {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(Npc::from(id.as_str()))
+    }
+}
+
+/// A character as written down in `assets/npcs.ron`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NpcDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    // Where the character is when a new game starts.
+    pub room: RoomType,
+    // Health at the start, also the most they can be healed to.
+    pub health: i32,
+    // What the character suffers from until it is treated.
+    #[serde(default)]
+    pub status_effects: Vec<StatusEffectType>,
+    // Shown on "examine", the first one whose conditions hold.
+    pub descriptions: Vec<Description>,
+    // Added to the room text when the player comes back to the character.
+    #[serde(default)]
+    pub presence: Vec<Description>,
+    // Shown when the character follows the player into a room.
+    pub follow_text: String,
+    // Shown when the character dies in front of the player.
+    pub death_text: String,
+    // File in `assets/dialogue/` that `tree` is read from.
+    pub dialogue: String,
+    #[serde(skip)]
+    pub tree: Dialogue,
+    // Shown when a conversation ends.
+    pub farewell: String,
+}
+
+pub type Npcs = HashMap<Npc, NpcDefinition>;
+
+/// How a character is doing right now.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NpcState {
+    pub room: RoomType,
+    pub health: i32,
+    pub status_effects: Vec<StatusEffectType>,
+    // Whether the character goes wherever the player goes.
+    pub following: bool,
+}
+
+impl NpcState {
+    pub fn is_alive(&self) -> bool {
+        self.health > 0
+    }
+}
+
+pub fn initialize_npcs(state: &mut State, npcs: &Npcs) {
+    state.npcs = npcs
+        .iter()
+        .map(|(npc, definition)| {
+            let npc_state = NpcState {
+                room: definition.room,
+                health: definition.health,
+                status_effects: definition.status_effects.clone(),
+                following: false,
+            };
+            (*npc, npc_state)
+        })
+        .collect();
+}
+
+/// Read the character list along with every character's dialogue tree.
+pub fn load_npcs(dir: &Path, rooms: &[RoomType]) -> io::Result<Npcs> {
+    let path = dir.join("npcs.ron");
+    let mut npcs: Npcs = load_ron(&path)?;
+    for (npc, definition) in &mut npcs {
+        if !rooms.contains(&definition.room) {
+            return Err(invalid_data(
+                &path,
+                format!("{} is in unknown room {}", npc, definition.room),
+            ));
+        }
+        let dialogue_path = dir.join("dialogue").join(&definition.dialogue);
+        let tree: Dialogue = load_ron(&dialogue_path)?;
+        tree.check().map_err(|err| invalid_data(&dialogue_path, err))?;
+        definition.tree = tree;
+    }
+    Ok(npcs)
+}
+
+/// Characters in the current room, dead or alive, sorted so listings don't
+/// depend on hash order.
+pub fn npcs_in_room(app: &App) -> Vec<Npc> {
+    let mut npcs: Vec<Npc> = app
+        .state
+        .npcs
+        .iter()
+        .filter(|(_, npc_state)| npc_state.room == app.state.current_room)
+        .map(|(npc, _)| *npc)
+        .collect();
+    npcs.sort();
+    npcs
+}
+
+pub fn npc_from_name(app: &App, name: &str) -> Option<Npc> {
+    app.npcs
+        .iter()
+        .find(|(_, definition)| {
+            definition.name.eq_ignore_ascii_case(name)
+                || definition.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name))
+        })
+        .map(|(npc, _)| *npc)
+}
+
+pub fn npc_name(app: &App, npc: Npc) -> &str {
+    app.npcs
+        .get(&npc)
+        .map_or(npc.id(), |definition| definition.name.as_str())
+}
+
+// The first of `descriptions` whose conditions all hold.
+fn pick(app: &App, descriptions: &[Description]) -> Option<String> {
+    descriptions
+        .iter()
+        .find(|description| description.conditions.iter().all(|condition| holds(app, condition)))
+        .map(|description| description.text.clone())
+}
+
+pub fn npc_timer() -> Timer {
+    Timer::new(
+        TimerType::Npc,
+        // Unused, because invisible.
+        "",
+        0,
+        NPC_INTERVAL,
+        Action::UpdateNpcs,
+        false,
+    )
+}
+
+/// Let every living character suffer from what ails them.
+pub fn update_npcs(app: &mut App) {
+    app.event_queue.schedule_timer(npc_timer());
+
+    let mut died = vec![];
+    for (npc, npc_state) in app.state.npcs.iter_mut() {
+        if !npc_state.is_alive() {
+            continue;
+        }
+        for effect_type in &npc_state.status_effects {
+            if let StatusEffectType::Bleeding = effect_type {
+                npc_state.health -= BLEEDING_DAMAGE;
+            }
+        }
+        if !npc_state.is_alive() {
+            died.push(*npc);
+        }
+    }
+    died.sort();
+    for npc in died {
+        app.event_queue.schedule_action(Action::NpcDied(npc));
+    }
+}
+
+pub fn npc_died(app: &mut App, npc: Npc) {
+    let npc_state = match app.state.npcs.get_mut(&npc) {
+        Some(npc_state) => npc_state,
+        None => return,
+    };
+    npc_state.health = 0;
+    npc_state.following = false;
+    if npc_state.room != app.state.current_room {
+        return;
+    }
+
+    if app.state.conversation.as_ref().map(|conversation| conversation.npc) == Some(npc) {
+        app.state.conversation = None;
+    }
+    let death_text = app.npcs[&npc].death_text.clone();
+    message(app, death_text, GameEventType::Failure);
+}
+
+/// Bring everyone following the player from `from` into the current room.
+pub fn move_followers(app: &mut App, from: RoomType) {
+    let to = app.state.current_room;
+    let mut followers: Vec<Npc> = app
+        .state
+        .npcs
+        .iter()
+        .filter(|(_, npc_state)| npc_state.following && npc_state.is_alive() && npc_state.room == from)
+        .map(|(npc, _)| *npc)
+        .collect();
+    followers.sort();
+    for npc in followers {
+        app.state.npcs.get_mut(&npc).unwrap().room = to;
+        let follow_text = app.npcs[&npc].follow_text.clone();
+        message(app, follow_text, GameEventType::Normal);
+    }
+}
+
+/// Mention who is in the room and how they are doing.
+pub fn describe_npcs_in_room(app: &mut App) {
+    for npc in npcs_in_room(app) {
+        if let Some(text) = pick(app, &app.npcs[&npc].presence) {
+            message(app, text, GameEventType::Normal);
+        }
+    }
+}
+
+pub fn examine_npc(app: &mut App, npc: Npc) {
+    let text = pick(app, &app.npcs[&npc].descriptions)
+        .unwrap_or_else(|| format!("There is nothing special about the {}.", npc_name(app, npc)));
+    message(app, text, GameEventType::Normal);
+}
+
+pub fn is_alive(app: &App, npc: Npc) -> bool {
+    app.state.npcs.get(&npc).map_or(false, NpcState::is_alive)
+}
@@ -1,17 +1,19 @@
 use tui::style::{Color, Style};
 use tui::widgets::Text;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum Item {
-    KeyCard,
-    Crowbar,
-}
+use super::status::{StatusEffect, StatusEffectType};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub health: i32,
     pub attack_strength: i32,
-    pub items: Vec<Item>,
+    pub oxygen: i32,
+    pub status_effects: Vec<StatusEffect>,
+    // Ids of the memories from `assets/memories.ron` recovered so far, in the
+    // order they came back.
+    pub memories: Vec<String>,
+    // Unknown until the player finds out who they are.
+    pub name: Option<String>,
 }
 
 impl Player {
@@ -30,12 +32,44 @@ impl Player {
         ]
     }
 
-    pub fn has_item(&self, item: Item) -> bool {
-        for owned_item in &self.items {
-            if item == *owned_item {
-                return true;
-            }
+    pub fn format_status_effects(&self) -> Vec<Text> {
+        if self.status_effects.is_empty() {
+            return vec![Text::raw("You feel fine.")];
+        }
+        self.status_effects
+            .iter()
+            .map(|effect| effect.format())
+            .collect()
+    }
+
+    /// Start an effect, or extend it if the player already suffers from it.
+    pub fn add_status_effect(&mut self, effect_type: StatusEffectType, duration: u64) {
+        match self
+            .status_effects
+            .iter_mut()
+            .find(|effect| effect.effect_type == effect_type)
+        {
+            Some(effect) => effect.remaining = effect.remaining.max(duration),
+            None => self
+                .status_effects
+                .push(StatusEffect::new(effect_type, duration)),
         }
-        false
+    }
+
+    pub fn has_status_effect(&self, effect_type: StatusEffectType) -> bool {
+        self.status_effects
+            .iter()
+            .any(|effect| effect.effect_type == effect_type)
+    }
+
+    /// Advance all effects by `dt` milliseconds and return the damage dealt.
+    pub fn tick_status_effects(&mut self, dt: u64) -> i32 {
+        let damage = self
+            .status_effects
+            .iter_mut()
+            .map(|effect| effect.tick(dt))
+            .sum();
+        self.status_effects.retain(|effect| !effect.is_over());
+        damage
     }
 }
@@ -0,0 +1,61 @@
+use tui::style::{Color, Style};
+use tui::widgets::Text;
+
+// Bleeding costs one health point this often.
+const BLEEDING_INTERVAL: u64 = 2000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Display, Serialize, Deserialize)]
+pub enum StatusEffectType {
+    Bleeding,
+    Cold,
+    #[strum(serialize = "Low oxygen")]
+    LowOxygen,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub effect_type: StatusEffectType,
+    // Milliseconds until the effect wears off.
+    pub remaining: u64,
+    pub elapsed: u64,
+}
+
+impl StatusEffect {
+    pub fn new(effect_type: StatusEffectType, duration: u64) -> Self {
+        StatusEffect {
+            effect_type,
+            remaining: duration,
+            elapsed: 0,
+        }
+    }
+
+    /// Advance the effect by `dt` milliseconds and return the damage it dealt.
+    pub fn tick(&mut self, dt: u64) -> i32 {
+        let dt = dt.min(self.remaining);
+        let before = self.elapsed;
+        self.elapsed += dt;
+        self.remaining -= dt;
+        match self.effect_type {
+            StatusEffectType::Bleeding => {
+                (self.elapsed / BLEEDING_INTERVAL - before / BLEEDING_INTERVAL) as i32
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.remaining == 0
+    }
+
+    pub fn format(&self) -> Text {
+        let color = match self.effect_type {
+            StatusEffectType::Bleeding => Color::Red,
+            StatusEffectType::Cold => Color::Cyan,
+            StatusEffectType::LowOxygen => Color::Magenta,
+        };
+        Text::styled(
+            format!("{} ({}s)\n", self.effect_type, (self.remaining + 999) / 1000),
+            Style::default().fg(color),
+        )
+    }
+}
@@ -21,14 +21,12 @@ pub struct Events {
 
 #[derive(Debug, Clone, Copy)]
 pub struct Config {
-    pub exit_key: Key,
     pub tick_rate: Duration,
 }
 
 impl Default for Config {
     fn default() -> Config {
         Config {
-            exit_key: Key::Esc,
             tick_rate: Duration::from_millis(100),
         }
     }
@@ -48,12 +46,11 @@ impl Events {
                 for evt in stdin.keys() {
                     match evt {
                         Ok(key) => {
+                            // Keys keep coming until the game quits, Esc
+                            // only opens menus.
                             if let Err(_) = tx.send(Event::Input(key)) {
                                 return;
                             }
-                            if key == config.exit_key {
-                                return;
-                            }
                         }
                         Err(_) => {}
                     }
@@ -2,7 +2,7 @@ use crate::action::Action;
 use crate::timer::{Timer, TimerType};
 use std::collections::VecDeque;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct EventQueue {
     pub actions: VecDeque<Action>,
     pub timers: Vec<Timer>,
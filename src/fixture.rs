@@ -0,0 +1,64 @@
+use crate::action::Action;
+use crate::game_event::GameEventType;
+use crate::trigger::{holds, Condition};
+use crate::App;
+
+/// Something built into a room that can be looked at but not taken, like
+/// the caskets in the cryobay or the enemy guarding a room.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    // Checked in order, the first one whose conditions hold is shown.
+    pub descriptions: Vec<Description>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Description {
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    pub text: String,
+}
+
+impl Fixture {
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(name))
+    }
+}
+
+/// The fixture in the current room going by `name`.
+pub fn fixture_from_name<'a>(app: &'a App, name: &str) -> Option<&'a Fixture> {
+    app.rooms[&app.state.current_room]
+        .definition()
+        .fixtures
+        .iter()
+        .find(|fixture| fixture.is_named(name))
+}
+
+pub fn examine_fixture(app: &mut App, name: &str) {
+    let text = match fixture_from_name(app, name) {
+        Some(fixture) => fixture
+            .descriptions
+            .iter()
+            .find(|description| {
+                description
+                    .conditions
+                    .iter()
+                    .all(|condition| holds(app, condition))
+            })
+            .map_or_else(
+                || format!("There is nothing special about the {}.", fixture.name),
+                |description| description.text.clone(),
+            ),
+        None => {
+            return app.event_queue.schedule_action(Action::Message(
+                format!("There is no {} here.", name),
+                GameEventType::Failure,
+            ))
+        }
+    };
+    app.event_queue
+        .schedule_action(Action::Message(text, GameEventType::Normal));
+}
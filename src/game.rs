@@ -0,0 +1,205 @@
+use std::io;
+
+use rand::Rng;
+
+use crate::action::Action;
+use crate::ai::{check_voice_lines, load_ai};
+use crate::door::load_doors;
+use crate::ending::load_endings;
+use crate::help::load_hints;
+use crate::memory::{check_memories, load_memories};
+use crate::entities::enemy::initialize_enemies;
+use crate::entities::{initialize_items, initialize_npcs, load_item_definitions, load_npcs, npc_timer};
+use crate::game_event::{GameEvent, GameEventType};
+use crate::global_handlers::handle_action;
+use crate::oxygen::oxygen_timer;
+use crate::room::{load_room_definitions, RoomType, START_ROOM};
+use crate::rooms::GenericRoom;
+use crate::save::latest_slot;
+use crate::script::Scripts;
+use crate::sound::AudioEvent;
+use crate::state::State;
+use crate::trigger::load_triggers;
+use crate::utils::assets_dir;
+use crate::App;
+
+/// Everything the game emitted while processing a batch of actions.
+#[derive(Debug, Default)]
+pub struct GameOutput {
+    // Log entries in the order they were written.
+    pub game_events: Vec<GameEvent>,
+    // Sounds and music changes for the frontend to play.
+    pub audio_events: Vec<AudioEvent>,
+}
+
+// Catch misspelled script names at startup instead of when a hook would run.
+fn check_scripts(app: &App) -> io::Result<()> {
+    let room_scripts = app.rooms.values().map(|room| &room.definition().script);
+    let item_scripts = app.items.values().map(|definition| &definition.script);
+    for script in room_scripts.chain(item_scripts) {
+        if let Some(script) = script {
+            if !app.scripts.contains(script) {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("unknown script {}", script),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The headless game engine.
+///
+/// Frontends feed it input lines and time deltas and present whatever comes
+/// back. It never touches a terminal, so it can be driven from scripts too.
+#[derive(Debug)]
+pub struct Game {
+    pub app: App,
+}
+
+impl Game {
+    pub fn new() -> io::Result<Self> {
+        Game::with_seed(rand::random())
+    }
+
+    /// Set up a game whose every random roll is determined by `seed`. Nothing
+    /// happens until `new_game` or a load starts it.
+    pub fn with_seed(seed: u64) -> io::Result<Self> {
+        let mut app = App::new(State::new(seed));
+
+        for (room_type, definition) in load_room_definitions(&assets_dir().join("rooms"))? {
+            app.rooms
+                .insert(room_type, Box::new(GenericRoom::new(definition)));
+        }
+
+        app.doors = load_doors(&assets_dir().join("doors.ron"), &app.rooms)?;
+        app.triggers = load_triggers(&assets_dir().join("triggers.ron"))?;
+        // Item interactions are triggers too, kept apart to stay readable.
+        app.triggers
+            .extend(load_triggers(&assets_dir().join("interactions.ron"))?);
+        app.endings = load_endings(&assets_dir())?;
+        app.hints = load_hints(&assets_dir().join("hints.ron"))?;
+        let room_types: Vec<RoomType> = app.rooms.keys().cloned().collect();
+        app.items = load_item_definitions(&assets_dir().join("items.ron"), &room_types)?;
+        app.npcs = load_npcs(&assets_dir(), &room_types)?;
+        app.ai = load_ai(&assets_dir().join("ai.ron"))?;
+        app.memories = load_memories(&assets_dir().join("memories.ron"))?;
+        app.scripts = Scripts::load(&assets_dir().join("scripts"))?;
+        check_scripts(&app)?;
+        check_voice_lines(&app)?;
+        check_memories(&app)?;
+
+        initialize_enemies(&mut app.state, &app.rooms);
+        initialize_items(&mut app.state, &app.items);
+        initialize_npcs(&mut app.state, &app.npcs);
+        app.state.ai_integrity = app.ai.integrity;
+
+        Ok(Game { app })
+    }
+
+    /// Wake the player up in the starting room.
+    pub fn new_game(&mut self) -> GameOutput {
+        self.app.event_queue.schedule_action(Action::Enter(START_ROOM));
+        self.app.event_queue.schedule_timer(oxygen_timer());
+        self.app.event_queue.schedule_timer(npc_timer());
+        self.update()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.app.state.game_over.is_some()
+    }
+
+    /// Throw the current run away and start a new one.
+    pub fn restart(&mut self) -> io::Result<GameOutput> {
+        // Derived from the current seed so a recorded session replays the same.
+        let autocorrect = self.app.autocorrect;
+        *self = Game::with_seed(self.app.state.rng.gen())?;
+        self.app.autocorrect = autocorrect;
+        Ok(self.new_game())
+    }
+
+    pub fn save(&mut self, slot: &str) -> GameOutput {
+        self.app.event_queue.schedule_action(Action::Save(slot.into()));
+        self.update()
+    }
+
+    pub fn load(&mut self, slot: &str) -> GameOutput {
+        self.app.event_queue.schedule_action(Action::Load(slot.into()));
+        self.update()
+    }
+
+    /// Continue from whatever slot was saved to last.
+    pub fn load_last_save(&mut self) -> GameOutput {
+        match latest_slot() {
+            Some(slot) => self.load(&slot),
+            None => GameOutput::default(),
+        }
+    }
+
+    /// The player started typing, so they are done reading the room text.
+    pub fn start_input(&mut self) -> GameOutput {
+        self.app
+            .event_queue
+            .schedule_action(Action::PlayerFinishedReading);
+        self.update()
+    }
+
+    /// Submit a line of input as if it had been typed into the command box.
+    pub fn input_line(&mut self, line: &str) -> GameOutput {
+        if line.is_empty() || self.is_over() {
+            return GameOutput::default();
+        }
+
+        let echo = GameEvent {
+            content: format!("\n>>> {}\n\n", line),
+            game_event_type: GameEventType::Normal,
+        };
+        self.app.log.push_front(echo.clone());
+        self.app
+            .event_queue
+            .schedule_action(Action::Command(line.into()));
+
+        let mut output = self.update();
+        output.game_events.insert(0, echo);
+        output
+    }
+
+    /// Advance all timers by `dt` milliseconds.
+    pub fn tick(&mut self, dt: u64) -> GameOutput {
+        // Timers stand still on the ending screen.
+        if self.is_over() {
+            return GameOutput::default();
+        }
+        self.app.event_queue.schedule_action(Action::Tick(dt));
+        self.update()
+    }
+
+    /// Process every pending action and collect what was emitted.
+    pub fn update(&mut self) -> GameOutput {
+        let mut output = GameOutput::default();
+
+        while let Some(next_action) = self.app.event_queue.get_next_action() {
+            let log_len = self.app.log.len();
+            match next_action {
+                Action::Audio(audio_event) => {
+                    output.audio_events.push(audio_event);
+                    continue;
+                }
+                // Loading swaps out the whole log, none of it is new.
+                Action::Load(_) => {
+                    handle_action(&mut self.app, next_action);
+                    continue;
+                }
+                _ => handle_action(&mut self.app, next_action),
+            }
+
+            // The log is newest-first, so the fresh entries sit at the front.
+            let new_events = self.app.log.len() - log_len;
+            output
+                .game_events
+                .extend(self.app.log.iter().take(new_events).rev().cloned());
+        }
+        output
+    }
+}
@@ -1,13 +1,15 @@
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum GameEventType {
     Combat,
     Normal,
     Success,
     Failure,
     Debug,
+    // Spoken by the ship's AI.
+    Ai,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameEvent {
     pub content: String,
     pub game_event_type: GameEventType,
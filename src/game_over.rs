@@ -0,0 +1,99 @@
+use tui::style::{Color, Modifier, Style};
+use tui::widgets::Text;
+
+use crate::ending::Ending;
+
+/// Why the game ended.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum GameOverCause {
+    Killed,
+    BledOut,
+    Suffocated,
+    // Went back into cryosleep before the reboot.
+    Casket,
+    // Was still awake when the reboot shut down life support.
+    Reboot,
+}
+
+impl GameOverCause {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            GameOverCause::Killed => "You were killed.",
+            GameOverCause::BledOut => "You bled out.",
+            GameOverCause::Suffocated => "You ran out of oxygen.",
+            GameOverCause::Casket => "You went back into cryosleep.",
+            GameOverCause::Reboot => "The reboot shut down life support around you.",
+        }
+    }
+
+    pub fn is_death(&self) -> bool {
+        match self {
+            GameOverCause::Casket => false,
+            _ => true,
+        }
+    }
+}
+
+/// Counted over a whole run and shown once it ends.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunStats {
+    // Milliseconds spent in the game.
+    pub play_time: u64,
+    pub commands: u32,
+    pub enemies_defeated: u32,
+    pub damage_taken: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct GameOver {
+    pub cause: GameOverCause,
+    pub ending: Option<Ending>,
+    pub stats: RunStats,
+    pub rooms_visited: usize,
+}
+
+impl GameOver {
+    /// The ending screen, with `can_load` telling whether a save exists.
+    pub fn format(&self, can_load: bool) -> Vec<Text> {
+        let title = if self.cause.is_death() {
+            "GAME OVER\n\n"
+        } else {
+            "THE END\n\n"
+        };
+        let seconds = self.stats.play_time / 1000;
+        let mut lines = vec![
+            Text::styled(
+                title,
+                Style::default().fg(Color::Red).modifier(Modifier::Bold),
+            ),
+            Text::raw(format!("{}\n\n", self.cause.describe())),
+        ];
+        if let Some(ref ending) = self.ending {
+            lines.push(Text::styled(
+                format!("Ending: {}\n\n", ending.title),
+                Style::default().fg(Color::Green),
+            ));
+            lines.push(Text::raw(format!("{}\n\n", ending.epilogue.trim_end())));
+        }
+        lines.extend(vec![
+            Text::raw(format!(
+                "Time played: {}:{:02}\n",
+                seconds / 60,
+                seconds % 60
+            )),
+            Text::raw(format!("Commands entered: {}\n", self.stats.commands)),
+            Text::raw(format!("Rooms visited: {}\n", self.rooms_visited)),
+            Text::raw(format!("Enemies defeated: {}\n", self.stats.enemies_defeated)),
+            Text::raw(format!("Damage taken: {}\n\n", self.stats.damage_taken)),
+            Text::styled("[1] Restart\n", Style::default().fg(Color::Yellow)),
+        ]);
+        if can_load {
+            lines.push(Text::styled(
+                "[2] Load last save\n",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        lines.push(Text::styled("[Esc] Quit\n", Style::default().fg(Color::Yellow)));
+        lines
+    }
+}
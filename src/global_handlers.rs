@@ -1,15 +1,39 @@
-use crate::action::{Action, ActionHandled};
+use crate::action::{Action, ActionHandled, Target};
+use crate::ai::{narrate, say};
+use crate::entities::{describe_npcs_in_room, examine_npc, item_name, npc_died, npc_name, update_npcs};
 use crate::game_event::{GameEvent, GameEventType};
-use crate::room::{closed_message, enter_room, RoomType};
+use crate::game_over::{GameOver, GameOverCause};
+use crate::inventory::{describe_items_in_room, drop_item, examine_item, show_inventory, take_item};
+use crate::memory::{remember, show_memories};
+use crate::door::{closed_message, door_between, is_blocked, use_item, use_item_on_door};
+use crate::dialogue::{choose, talk};
+use crate::ending::{pick_ending, unlock_ending};
+use crate::fixture::examine_fixture;
+use crate::room::{change_music, enter_room};
+use crate::oxygen::{breathe, use_oxygen_item};
+use crate::save::{load_game, save_game};
+use crate::script::{run_tick_hooks, run_use_hooks};
 use crate::sound::{AudioEvent, Effect};
 use crate::timer::TimerType;
+use crate::trigger::run_triggers;
 use crate::App;
-use crate::room;
 
 // Handle game actions here (Timers).
 pub fn handle_action(mut app: &mut App, next_action: Action) {
-    if app.try_handle_room_action(&next_action).is_some() {
-        return
+    // Once the game is over only messages and loading get through.
+    if app.state.game_over.is_some() {
+        match next_action {
+            Action::Message(..) | Action::Load(_) => {}
+            _ => return,
+        }
+    }
+
+    // Entering is left to `enter_room`, so locked or missing doors don't count.
+    let entering = if let Action::Enter(_) = next_action { true } else { false };
+    if !entering {
+        if let ActionHandled::Handled = run_triggers(app, &next_action) {
+            return;
+        }
     }
 
     // Handle system and global actions here.
@@ -22,35 +46,121 @@ pub fn handle_action(mut app: &mut App, next_action: Action) {
             })
         }
         Action::Rebooted => {
+            // Life support goes down with the rest of the ship.
+            app.state.player.oxygen = 0;
+            say(app, "initiating_reboot");
             app.event_queue.schedule_action(Action::Message(
-                String::from("\"Initiating reboot.\" The room goes black. You notice a coldness quickly creeping into the air and start to shiver. As the air gets thinner and thinner, you slowly slip into unconsciousness."),
+                String::from("The room goes black. You notice a coldness quickly creeping into the air and start to shiver. As the air gets thinner and thinner, you slowly slip into unconsciousness."),
                 GameEventType::Failure,
             ));
+            app.event_queue
+                .schedule_action(Action::PlayerDied(GameOverCause::Reboot));
         }
         Action::Enter(room_type) => {
-            if app.rooms.get(&room_type).unwrap().is_opened() || cfg!(debug_assertions) {
-                if room_type != RoomType::Corridor {
-                    app.event_queue
-                        .schedule_action(Action::Audio(AudioEvent::Effect(Effect::Door)));
+            // Waking up in the first room happens without a door.
+            let door = door_between(&app.doors, app.state.current_room, room_type);
+            match door {
+                // Debug builds skip locks, but not an enemy in the way.
+                Some(door) if (door.locked && !cfg!(debug_assertions)) || is_blocked(app, door) => {
+                    app.event_queue.schedule_action(Action::Message(
+                        closed_message(door, &app.rooms, room_type),
+                        GameEventType::Failure,
+                    ));
+                }
+                _ => {
+                    if door.map_or(true, |door| door.definition.sound) {
+                        app.event_queue
+                            .schedule_action(Action::Audio(AudioEvent::Effect(Effect::Door)));
+                    }
+                    enter_room(&mut app, room_type);
                 }
-                enter_room(&mut app, room_type);
-            } else {
-                app.event_queue.schedule_action(Action::Message(
-                    closed_message(room_type),
-                    GameEventType::Failure,
-                ));
             }
         }
         Action::Leave(_) => {}
         Action::ShowEnterText => {
-            app.event_queue.schedule_action(Action::Message(
-                room::room_intro_text(app.state.current_room).0.into(),
-                GameEventType::Normal,
-            ));
+            let enter_text = app.rooms[&app.state.current_room].definition().enter_text.clone();
+            narrate(app, &enter_text);
+            describe_items_in_room(app);
+            describe_npcs_in_room(app);
+        }
+        Action::PickUp(item) => take_item(app, item),
+        Action::Drop(item) => drop_item(app, item),
+        Action::Examine(item) => examine_item(app, item),
+        Action::ExamineFixture(name) => examine_fixture(app, &name),
+        Action::ExamineNpc(npc) => examine_npc(app, npc),
+        Action::UpdateNpcs => update_npcs(app),
+        Action::NpcDied(npc) => npc_died(app, npc),
+        Action::ShowInventory => show_inventory(app),
+        Action::ShowMemories => show_memories(app),
+        Action::Remember(id) => remember(app, &id),
+        Action::Talk(npc) => talk(app, npc),
+        Action::Choose(number) => choose(app, number),
+        Action::UseItem(item) => {
+            if !run_use_hooks(app, item) && !use_oxygen_item(app, item) {
+                use_item(app, item);
+            }
+        }
+        // Only reached when no interaction in `assets/interactions.ron` applies.
+        Action::UseOn(item, target) => {
+            let name = item_name(&app.items, item).to_string();
+            let failure = if !app.state.has_item(item) {
+                format!("You don't have a {}.", name)
+            } else {
+                match target {
+                    Target::Door(room_type) => return use_item_on_door(app, item, room_type),
+                    Target::Enemy => format!("Using the {} won't stop it. Try to attack or dodge.", name),
+                    Target::Item(other) => format!(
+                        "Nothing happens when you use the {} on the {}.",
+                        name,
+                        item_name(&app.items, other)
+                    ),
+                    Target::Npc(npc) => format!(
+                        "The {} has no use for the {}.",
+                        npc_name(app, npc),
+                        name
+                    ),
+                    Target::Terminal => format!("The terminal has no use for the {}.", name),
+                    Target::Casket => format!("You can't do anything to the casket with the {}.", name),
+                    Target::Console => format!("The console has no use for the {}.", name),
+                }
+            };
+            app.event_queue
+                .schedule_action(Action::Message(failure, GameEventType::Failure));
+        }
+        Action::Command(tokens) => {
+            app.state.stats.commands += 1;
+            app.try_handle_command(tokens);
         }
-        Action::Command(tokens) => app.try_handle_command(tokens),
+        Action::Save(slot) => match save_game(app, &slot) {
+            Ok(()) => app.event_queue.schedule_action(Action::Message(
+                format!("Game saved to slot \"{}\".", slot),
+                GameEventType::Success,
+            )),
+            Err(err) => app.event_queue.schedule_action(Action::Message(
+                format!("Could not save the game: {}.", err),
+                GameEventType::Failure,
+            )),
+        },
+        Action::Load(slot) => match load_game(app, &slot) {
+            Ok(()) => {
+                let current_room = app.state.current_room;
+                change_music(app, current_room);
+                app.event_queue.schedule_action(Action::Message(
+                    format!("Game loaded from slot \"{}\".", slot),
+                    GameEventType::Success,
+                ));
+            }
+            Err(err) => app.event_queue.schedule_action(Action::Message(
+                format!("Could not load the game: {}.", err),
+                GameEventType::Failure,
+            )),
+        },
         Action::EnemyAttack => {
-            if let Some(ref enemy) = app.state.get_current_enemy(app.state.current_room) {
+            // Another attack from the same update may already have been fatal.
+            if app.state.player.health <= 0 {
+                return;
+            }
+            if let Some(enemy) = app.state.enemies.get(&app.state.current_room) {
                 let timers = enemy.get_attack_timers(0);
                 for timer in timers {
                     app.event_queue.schedule_timer(timer);
@@ -59,23 +169,28 @@ pub fn handle_action(mut app: &mut App, next_action: Action) {
                 app.log.push_front(GameEvent {
                     content: format!(
                         "{} You lose {} HP.\n",
-                        enemy.get_enemy_attack_message(),
+                        enemy.get_enemy_attack_message(&mut app.state.rng),
                         enemy.get_attack_strength(),
                     ),
                     game_event_type: GameEventType::Combat,
                 });
 
                 app.state.player.health -= enemy.get_attack_strength();
+                app.state.stats.damage_taken += enemy.get_attack_strength();
+                if let Some((effect_type, duration)) = enemy.get_wound() {
+                    app.state.player.add_status_effect(effect_type, duration);
+                }
                 if app.state.player.health <= 0 {
-                    app.event_queue.schedule_action(Action::PlayerDied);
+                    app.event_queue
+                        .schedule_action(Action::PlayerDied(GameOverCause::Killed));
                 }
             }
         }
         Action::Attack => {
             let damage = app.state.player.attack_strength;
-            if let Some(ref mut enemy) = app.state.get_current_enemy_mut(app.state.current_room) {
+            if let Some(enemy) = app.state.enemies.get_mut(&app.state.current_room) {
                 enemy.reduce_health(damage);
-                let attack_message = enemy.get_attack_message();
+                let attack_message = enemy.get_attack_message(&mut app.state.rng);
                 let enemy_type = enemy.get_enemy_type();
                 if enemy.get_health() <= 0 {
                     app.event_queue
@@ -89,6 +204,7 @@ pub fn handle_action(mut app: &mut App, next_action: Action) {
                     app.event_queue
                         .emplace_timers(TimerType::EnemyAttack, vec![]);
                     app.state.enemies.remove(&app.state.current_room);
+                    app.state.stats.enemies_defeated += 1;
                 }
                 app.log.push_front(GameEvent {
                     content: format!("{}\n", attack_message),
@@ -101,26 +217,47 @@ pub fn handle_action(mut app: &mut App, next_action: Action) {
                 ));
             }
         }
-        Action::PlayerDied => {
+        Action::Breathe => breathe(app),
+        Action::PlayerDied(cause) => {
             app.event_queue.schedule_action(Action::Message(
                 String::from("You died."),
                 GameEventType::Failure,
             ));
+            app.event_queue.schedule_action(Action::GameOver(cause));
+        }
+        Action::GameOver(cause) => {
+            let ending = pick_ending(app, cause).cloned();
+            if let Some(ref ending) = ending {
+                if let Err(err) = unlock_ending(&ending.id) {
+                    app.log.push_front(GameEvent {
+                        content: format!("Could not record the ending: {}\n", err),
+                        game_event_type: GameEventType::Debug,
+                    });
+                }
+            }
+            app.state.game_over = Some(GameOver {
+                cause,
+                ending,
+                stats: app.state.stats.clone(),
+                rooms_visited: app.rooms.values().filter(|room| room.is_visited()).count(),
+            });
         }
         Action::Dodge => {
-            let mut attack_timers = app.event_queue.get_timers(TimerType::EnemyAttack);
-            if attack_timers.is_empty() {
-                if app.state.enemies.get(&app.state.current_room).is_some() {
+            // Attack timers can outlive the enemy's room, so go by the enemy.
+            let enemy_type = match app.state.get_current_enemy(app.state.current_room) {
+                Some(enemy) => enemy.get_enemy_type(),
+                None => {
                     app.event_queue.schedule_action(Action::Message(
-                        String::from(
-                            "You dodge the attack. The enemy calmly analyses your movements.",
-                        ),
+                        String::from("There is nothing to dodge."),
                         GameEventType::Failure,
                     ));
                     return;
                 }
+            };
+            let mut attack_timers = app.event_queue.get_timers(TimerType::EnemyAttack);
+            if attack_timers.is_empty() {
                 app.event_queue.schedule_action(Action::Message(
-                    String::from("You dodge the attack of your own paranoia..."),
+                    String::from("You dodge the attack. The enemy calmly analyses your movements."),
                     GameEventType::Failure,
                 ));
                 return;
@@ -133,13 +270,6 @@ pub fn handle_action(mut app: &mut App, next_action: Action) {
                 app.event_queue
                     .emplace_timers(TimerType::EnemyAttack, enemy.get_attack_timers(0));
             }
-
-            let enemy_type = app
-                .state
-                .enemies
-                .get(&app.state.current_room)
-                .unwrap()
-                .get_enemy_type();
             app.event_queue.schedule_action(Action::Message(
                 String::from(format!("You dodge the {:?}'s attack.", enemy_type)),
                 GameEventType::Success,
@@ -147,6 +277,16 @@ pub fn handle_action(mut app: &mut App, next_action: Action) {
         }
         Action::Tick(dt) => {
             app.event_queue.tick(dt);
+            app.state.stats.play_time += dt;
+            let was_alive = app.state.player.health > 0;
+            let damage = app.state.player.tick_status_effects(dt);
+            app.state.player.health -= damage;
+            app.state.stats.damage_taken += damage;
+            if was_alive && app.state.player.health <= 0 {
+                app.event_queue
+                    .schedule_action(Action::PlayerDied(GameOverCause::BledOut));
+            }
+            run_tick_hooks(app, dt);
         }
         Action::PlayerFinishedReading => {
             let room_type = app.state.current_room;
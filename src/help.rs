@@ -0,0 +1,274 @@
+use std::io;
+use std::path::Path;
+
+use crate::action::Action;
+use crate::door::exits;
+use crate::entities::npcs_in_room;
+use crate::game_event::GameEventType;
+use crate::inventory::is_in_room;
+use crate::room::RoomType;
+use crate::save::slots;
+use crate::trigger::{holds, Condition};
+use crate::utils::load_ron;
+use crate::App;
+
+// When a command does something, as opposed to only explaining why it can't.
+enum Context {
+    Always,
+    // There is a door out of the room.
+    Exits,
+    // An enemy is in the room.
+    Enemy,
+    // An item lies around in the room.
+    ItemsHere,
+    Carrying,
+    // An item or fixture is in the room, or an item is carried.
+    ItemsAround,
+    InRoom(&'static str),
+    // Someone is in the room to talk to.
+    Npc,
+    // There is a saved game.
+    Slots,
+}
+
+/// How to use one command.
+pub struct Usage {
+    pub verb: &'static str,
+    pub usage: &'static str,
+    pub summary: &'static str,
+    pub examples: &'static [&'static str],
+    context: Context,
+}
+
+pub static USAGES: &'static [Usage] = &[
+    Usage {
+        verb: "enter",
+        usage: "enter <room or direction>",
+        summary: "Walk through a door into the next room.",
+        examples: &["enter slush lobby", "go north"],
+        context: Context::Exits,
+    },
+    Usage {
+        verb: "look",
+        usage: "look [at <thing>]",
+        summary: "Look around the room, or at something in it.",
+        examples: &["look", "look at the terminal"],
+        context: Context::Always,
+    },
+    Usage {
+        verb: "examine",
+        usage: "examine <thing>",
+        summary: "Take a closer look at an item, an enemy or something in the room.",
+        examples: &["examine crowbar", "x caskets"],
+        context: Context::ItemsAround,
+    },
+    Usage {
+        verb: "take",
+        usage: "take <item>",
+        summary: "Pick up an item lying in the room.",
+        examples: &["take crowbar", "pick up the canister"],
+        context: Context::ItemsHere,
+    },
+    Usage {
+        verb: "drop",
+        usage: "drop <item>",
+        summary: "Put down an item you carry.",
+        examples: &["drop crowbar"],
+        context: Context::Carrying,
+    },
+    Usage {
+        verb: "use",
+        usage: "use <item> [on <target>]",
+        summary: "Use an item you carry, on its own or on a door, an enemy or another item.",
+        examples: &["use oxygen canister", "use the crowbar on the vent"],
+        context: Context::Carrying,
+    },
+    Usage {
+        verb: "use",
+        usage: "use terminal",
+        summary: "Work the terminal in cryo control.",
+        examples: &["use terminal"],
+        context: Context::InRoom("cryocontrol"),
+    },
+    Usage {
+        verb: "use",
+        usage: "use casket",
+        summary: "Climb back into your cryosleep casket.",
+        examples: &["use casket"],
+        context: Context::InRoom("cryobay"),
+    },
+    Usage {
+        verb: "use",
+        usage: "use console",
+        summary: "Work the commlink console.",
+        examples: &["use console", "use the fuse on the console"],
+        context: Context::InRoom("commlink"),
+    },
+    Usage {
+        verb: "attack",
+        usage: "attack [<enemy>] [with <item>]",
+        summary: "Strike the enemy in the room, bare-handed or with something you carry.",
+        examples: &["attack the rat with the crowbar", "attack"],
+        context: Context::Enemy,
+    },
+    Usage {
+        verb: "dodge",
+        usage: "dodge",
+        summary: "Get out of the way of the enemy's next attack.",
+        examples: &["dodge"],
+        context: Context::Enemy,
+    },
+    Usage {
+        verb: "talk",
+        usage: "talk [to <someone>]",
+        summary: "Start a conversation, then answer by typing a number.",
+        examples: &["talk to the pale figure", "talk"],
+        context: Context::Npc,
+    },
+    Usage {
+        verb: "inventory",
+        usage: "inventory",
+        summary: "List what you carry.",
+        examples: &["inventory", "i"],
+        context: Context::Always,
+    },
+    Usage {
+        verb: "memories",
+        usage: "memories",
+        summary: "Go over what you remember from before waking up.",
+        examples: &["memories", "recall"],
+        context: Context::Always,
+    },
+    Usage {
+        verb: "save",
+        usage: "save <slot>",
+        summary: "Save the game under a name of your choice.",
+        examples: &["save before-the-vent"],
+        context: Context::Always,
+    },
+    Usage {
+        verb: "load",
+        usage: "load <slot>",
+        summary: "Go back to a saved game.",
+        examples: &["load before-the-vent"],
+        context: Context::Slots,
+    },
+    Usage {
+        verb: "help",
+        usage: "help [<command>]",
+        summary: "List the commands, or explain one of them.",
+        examples: &["help", "help use"],
+        context: Context::Always,
+    },
+    Usage {
+        verb: "hint",
+        usage: "hint",
+        summary: "Get a nudge towards what to do next.",
+        examples: &["hint"],
+        context: Context::Always,
+    },
+];
+
+fn applies(app: &App, context: &Context) -> bool {
+    let room = app.state.current_room;
+    match context {
+        Context::Always => true,
+        Context::Exits => !exits(&app.doors, room).is_empty(),
+        Context::Enemy => app.state.enemies.contains_key(&room),
+        Context::ItemsHere => app
+            .items
+            .keys()
+            .any(|item| !app.state.has_item(*item) && is_in_room(app, *item)),
+        Context::Carrying => !app.state.carried_items().is_empty(),
+        Context::ItemsAround => {
+            !app.rooms[&room].definition().fixtures.is_empty()
+                || !app.state.carried_items().is_empty()
+                || app.items.keys().any(|item| is_in_room(app, *item))
+        }
+        Context::InRoom(id) => room == RoomType::from(*id),
+        Context::Npc => !npcs_in_room(app).is_empty(),
+        Context::Slots => !slots().is_empty(),
+    }
+}
+
+/// Every command with what it does, for places without a game to look at.
+pub fn command_list() -> String {
+    USAGES
+        .iter()
+        .map(|usage| format!("{:<32}{}", usage.usage, usage.summary))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The answer to "help", or to "help <verb>" when `verb` is given. Commands
+/// that would do something right now are marked and shown in green.
+pub fn help(app: &App, verb: Option<&str>, synonyms: &[&str]) -> Vec<Action> {
+    let usages: Vec<&Usage> = USAGES
+        .iter()
+        .filter(|usage| verb.map_or(true, |verb| usage.verb == verb))
+        .collect();
+    if usages.is_empty() {
+        return vec![Action::Message(
+            format!(
+                "There is no command \"{}\". Type \"help\" to list them all.",
+                verb.unwrap_or_default()
+            ),
+            GameEventType::Failure,
+        )];
+    }
+
+    let mut actions = vec![Action::Message(
+        String::from("Commands marked with * do something here:"),
+        GameEventType::Normal,
+    )];
+    for usage in usages {
+        let (marker, game_event_type) = if applies(app, &usage.context) {
+            ("*", GameEventType::Success)
+        } else {
+            (" ", GameEventType::Normal)
+        };
+        let mut text = format!("{} {:<32}{}", marker, usage.usage, usage.summary);
+        // The full list only shows one example per command, to stay short.
+        let examples = match verb {
+            Some(_) => usage.examples,
+            None => &usage.examples[..1],
+        };
+        text += &format!("\n  For example: {}", examples.join(", "));
+        actions.push(Action::Message(text, game_event_type));
+    }
+
+    match verb {
+        Some(_) if synonyms.len() > 1 => actions.push(Action::Message(
+            format!("Also understood as: {}.", synonyms[1..].join(", ")),
+            GameEventType::Normal,
+        )),
+        Some(_) => {}
+        None => actions.push(Action::Message(
+            String::from("Type \"help <command>\" for more examples, or \"hint\" if you are stuck."),
+            GameEventType::Normal,
+        )),
+    }
+    actions
+}
+
+/// A nudge towards the next step, as written down in `assets/hints.ron`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Hint {
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    pub text: String,
+}
+
+pub fn load_hints(path: &Path) -> io::Result<Vec<Hint>> {
+    load_ron(path)
+}
+
+/// The first hint, in file order, whose conditions all hold.
+pub fn hint(app: &App) -> Action {
+    let text = app
+        .hints
+        .iter()
+        .find(|hint| hint.conditions.iter().all(|condition| holds(app, condition)))
+        .map_or("You're on your own from here.", |hint| hint.text.as_str());
+    Action::Message(format!("Hint: {}", text), GameEventType::Normal)
+}
@@ -0,0 +1,158 @@
+use tui::style::{Color, Style};
+use tui::widgets::Text;
+
+use crate::entities::{item_name, Item, ItemLocation};
+use crate::game_event::GameEventType;
+use crate::utils::message;
+use crate::App;
+
+// How much the player can carry at once.
+pub const MAX_CARRY_WEIGHT: u32 = 10;
+
+/// Whether `item` lies in the current room, possibly inside a container.
+pub fn is_in_room(app: &App, item: Item) -> bool {
+    match app.state.item_locations.get(&item) {
+        Some(ItemLocation::Room(room_type)) => *room_type == app.state.current_room,
+        Some(ItemLocation::Container(container)) => {
+            is_in_room(app, *container) || app.state.has_item(*container)
+        }
+        _ => false,
+    }
+}
+
+fn contents(app: &App, container: Item) -> Vec<Item> {
+    let mut contents: Vec<Item> = app
+        .state
+        .item_locations
+        .iter()
+        .filter(|(_, location)| **location == ItemLocation::Container(container))
+        .map(|(item, _)| *item)
+        .collect();
+    contents.sort();
+    contents
+}
+
+fn list(app: &App, items: &[Item]) -> String {
+    items
+        .iter()
+        .map(|item| item_name(&app.items, *item))
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+pub fn carried_weight(app: &App) -> u32 {
+    app.state
+        .carried_items()
+        .iter()
+        .map(|item| app.items[item].weight)
+        .sum()
+}
+
+/// The items lying around in the current room, not counting container contents.
+pub fn items_in_room(app: &App) -> Vec<Item> {
+    let here = ItemLocation::Room(app.state.current_room);
+    let mut items: Vec<Item> = app
+        .state
+        .item_locations
+        .iter()
+        .filter(|(_, location)| **location == here)
+        .map(|(item, _)| *item)
+        .collect();
+    items.sort();
+    items
+}
+
+pub fn take_item(app: &mut App, item: Item) {
+    let name = item_name(&app.items, item).to_string();
+    if app.state.has_item(item) {
+        message(app, format!("You already have the {}.", name), GameEventType::Failure);
+    } else if !is_in_room(app, item) {
+        message(app, format!("There is no {} here.", name), GameEventType::Failure);
+    } else if carried_weight(app) + app.items[&item].weight > MAX_CARRY_WEIGHT {
+        message(
+            app,
+            format!("The {} is too heavy to carry along with everything else.", name),
+            GameEventType::Failure,
+        );
+    } else {
+        app.state.item_locations.insert(item, ItemLocation::Player);
+        message(app, format!("You pick up the {}.", name), GameEventType::Success);
+    }
+}
+
+pub fn drop_item(app: &mut App, item: Item) {
+    let name = item_name(&app.items, item).to_string();
+    if app.state.has_item(item) {
+        let here = ItemLocation::Room(app.state.current_room);
+        app.state.item_locations.insert(item, here);
+        message(app, format!("You drop the {}.", name), GameEventType::Success);
+    } else {
+        message(app, format!("You don't have a {}.", name), GameEventType::Failure);
+    }
+}
+
+pub fn examine_item(app: &mut App, item: Item) {
+    let name = item_name(&app.items, item).to_string();
+    if !app.state.has_item(item) && !is_in_room(app, item) {
+        message(app, format!("There is no {} here.", name), GameEventType::Failure);
+        return;
+    }
+
+    let definition = &app.items[&item];
+    let mut text = definition.description.clone();
+    if definition.container {
+        let contents = contents(app, item);
+        if contents.is_empty() {
+            text += &format!("\nThe {} is empty.", name);
+        } else {
+            text += &format!("\nInside the {}: {}.", name, list(app, &contents));
+        }
+    }
+    message(app, text, GameEventType::Normal);
+}
+
+pub fn show_inventory(app: &mut App) {
+    let carried = app.state.carried_items();
+    let text = if carried.is_empty() {
+        String::from("You are not carrying anything.")
+    } else {
+        format!(
+            "You are carrying: {}. ({}/{} weight)",
+            list(app, &carried),
+            carried_weight(app),
+            MAX_CARRY_WEIGHT
+        )
+    };
+    message(app, text, GameEventType::Normal);
+}
+
+/// Mention what is lying around, so items can be found without a hint.
+pub fn describe_items_in_room(app: &mut App) {
+    let items = items_in_room(app);
+    if !items.is_empty() {
+        let text = format!("You notice: {}.", list(app, &items));
+        message(app, text, GameEventType::Normal);
+    }
+}
+
+/// The carried items for the inventory panel, one per line.
+pub fn format_inventory(app: &App) -> Vec<Text> {
+    let carried = app.state.carried_items();
+    if carried.is_empty() {
+        return vec![Text::raw("Nothing.")];
+    }
+
+    let mut lines = vec![];
+    for item in carried {
+        let definition = &app.items[&item];
+        lines.push(Text::styled(
+            definition.name.clone(),
+            Style::default().fg(Color::Yellow),
+        ));
+        match definition.summary {
+            Some(ref summary) => lines.push(Text::raw(format!(" - {}\n", summary))),
+            None => lines.push(Text::raw("\n")),
+        }
+    }
+    lines
+}
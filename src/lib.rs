@@ -0,0 +1,117 @@
+extern crate num;
+
+use std::collections::VecDeque;
+use tui::layout::Rect;
+
+#[macro_use]
+extern crate strum_macros;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+
+pub mod action;
+pub mod ai;
+pub mod commands;
+pub mod dialogue;
+pub mod door;
+pub mod ending;
+pub mod entities;
+pub mod event_queue;
+pub mod fixture;
+pub mod game;
+pub mod game_event;
+pub mod game_over;
+pub mod help;
+pub mod global_handlers;
+pub mod inventory;
+pub mod memory;
+pub mod oxygen;
+pub mod room;
+pub mod rooms;
+pub mod save;
+pub mod script;
+pub mod settings;
+pub mod sound;
+pub mod state;
+pub mod timer;
+pub mod trigger;
+pub mod utils;
+
+use crate::action::{Action, ActionHandled};
+use crate::ai::ShipAi;
+use crate::commands::try_handle_command;
+use crate::door::Doors;
+use crate::ending::Ending;
+use crate::entities::{Items, Npcs};
+use crate::event_queue::EventQueue;
+use crate::game_event::{GameEvent, GameEventType};
+use crate::help::Hint;
+use crate::memory::Memories;
+use crate::room::Rooms;
+use crate::script::Scripts;
+use crate::state::State;
+use crate::trigger::Trigger;
+
+pub use crate::game::{Game, GameOutput};
+
+#[derive(Debug)]
+pub struct App {
+    // The size of the console window.
+    pub size: Rect,
+    // The system event, like rendering stuff in the console.
+    pub log: VecDeque<GameEvent>,
+    // The global game state.
+    pub state: State,
+    // The list of rooms.
+    pub rooms: Rooms,
+    // The doors between rooms and whether they are locked.
+    pub doors: Doors,
+    // Story beats and puzzle logic declared in `assets/triggers.ron`.
+    pub triggers: Vec<Trigger>,
+    // Every way the story can end, in the order they are checked.
+    pub endings: Vec<Ending>,
+    // Nudges for the "hint" command, the first that fits is given.
+    pub hints: Vec<Hint>,
+    // What the data files say about each item.
+    pub items: Items,
+    // The characters to talk to, with their dialogue trees.
+    pub npcs: Npcs,
+    // The ship's AI and everything it can say.
+    pub ai: ShipAi,
+    // Everything the player can remember, by id.
+    pub memories: Memories,
+    // Compiled scripts that rooms and items hook into.
+    pub scripts: Scripts,
+    // The action event queue.
+    pub event_queue: EventQueue,
+    // Run commands with a typo fixed instead of only suggesting the fix.
+    pub autocorrect: bool,
+}
+
+impl App {
+    pub fn new(state: State) -> Self {
+        App {
+            size: Default::default(),
+            log: Default::default(),
+            state: state,
+            rooms: Default::default(),
+            doors: Default::default(),
+            triggers: Default::default(),
+            endings: Default::default(),
+            hints: Default::default(),
+            items: Default::default(),
+            npcs: Default::default(),
+            ai: Default::default(),
+            memories: Default::default(),
+            scripts: Default::default(),
+            event_queue: Default::default(),
+            autocorrect: false,
+        }
+    }
+
+    pub fn try_handle_command(&mut self, tokens: String) {
+        let actions = try_handle_command(tokens, self);
+        self.event_queue.schedule_actions(actions);
+    }
+}
@@ -0,0 +1,215 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use unicode_width::UnicodeWidthStr;
+
+// How many commands are remembered across sessions.
+const MAX_HISTORY: usize = 100;
+
+/// Commands entered in earlier sessions, oldest first.
+pub fn load_history(path: &PathBuf) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|contents| contents.lines().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+// The longest start that all `lines` share.
+fn common_prefix(lines: &[String]) -> String {
+    let first = &lines[0];
+    let mut end = first.len();
+    for line in &lines[1..] {
+        end = first[..end]
+            .char_indices()
+            .zip(line.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(end.min(line.len()), |((index, _), _)| index);
+    }
+    first[..end].to_string()
+}
+
+/// The command box: a single line with a cursor and a history.
+pub struct LineEditor {
+    text: String,
+    // Byte offset into `text`, always on a char boundary.
+    cursor: usize,
+    history: Vec<String>,
+    // The history entry shown while browsing with Up and Down.
+    browsing: Option<usize>,
+    // What was typed before browsing started, restored when going past the end.
+    draft: String,
+    // Where the history is kept, if it should outlive the session.
+    history_file: Option<PathBuf>,
+    // Candidates left over from the last ambiguous completion.
+    pub completions: Vec<String>,
+}
+
+impl LineEditor {
+    pub fn new(history: Vec<String>, history_file: Option<PathBuf>) -> Self {
+        LineEditor {
+            text: String::new(),
+            cursor: 0,
+            history,
+            browsing: None,
+            draft: String::new(),
+            history_file,
+            completions: vec![],
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Where the terminal cursor goes, in columns from the start of the line.
+    pub fn cursor_column(&self) -> usize {
+        self.text[..self.cursor].width()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.cursor = text.len();
+        self.text = text;
+        self.completions.clear();
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.text[..self.cursor]
+            .char_indices()
+            .last()
+            .map_or(0, |(index, _)| index)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+        self.completions.clear();
+    }
+
+    pub fn backspace(&mut self) {
+        let start = self.previous_boundary();
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+        self.completions.clear();
+    }
+
+    pub fn delete(&mut self) {
+        let end = self.next_boundary();
+        self.text.replace_range(self.cursor..end, "");
+        self.completions.clear();
+    }
+
+    /// Remove the word before the cursor, along with the spaces after it.
+    pub fn delete_word(&mut self) {
+        let before = self.text[..self.cursor].trim_end_matches(' ');
+        let start = before.rfind(' ').map_or(0, |index| index + 1);
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+        self.completions.clear();
+    }
+
+    pub fn left(&mut self) {
+        self.cursor = self.previous_boundary();
+    }
+
+    pub fn right(&mut self) {
+        self.cursor = self.next_boundary();
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.text.len();
+    }
+
+    /// Step back to the previous command in the history.
+    pub fn previous(&mut self) {
+        let index = match self.browsing {
+            Some(0) => return,
+            Some(index) => index - 1,
+            None if self.history.is_empty() => return,
+            None => {
+                self.draft = self.text.clone();
+                self.history.len() - 1
+            }
+        };
+        self.browsing = Some(index);
+        self.set_text(self.history[index].clone());
+    }
+
+    /// Step forward in the history, back to the draft after the newest entry.
+    pub fn next(&mut self) {
+        match self.browsing {
+            Some(index) if index + 1 < self.history.len() => {
+                self.browsing = Some(index + 1);
+                self.set_text(self.history[index + 1].clone());
+            }
+            Some(_) => {
+                self.browsing = None;
+                let draft = self.draft.split_off(0);
+                self.set_text(draft);
+            }
+            None => {}
+        }
+    }
+
+    /// Finish the line with one of `candidates`, each a full line starting
+    /// with what was typed. Several candidates complete as far as they agree.
+    pub fn complete(&mut self, mut candidates: Vec<String>) {
+        candidates.sort();
+        candidates.dedup();
+        let text = match candidates.len() {
+            0 => return,
+            // Verbs are followed by an object, objects end the line.
+            1 if candidates[0].contains(' ') => candidates[0].clone(),
+            1 => candidates[0].clone() + " ",
+            _ => common_prefix(&candidates),
+        };
+        if text.len() >= self.text.len() {
+            self.set_text(text);
+        }
+        if candidates.len() > 1 {
+            self.completions = candidates;
+        }
+    }
+
+    /// Take the line out of the editor and remember it.
+    pub fn submit(&mut self) -> String {
+        let line = self.text.split_off(0);
+        self.cursor = 0;
+        self.browsing = None;
+        self.completions.clear();
+
+        if self.history.last() != Some(&line) {
+            self.history.push(line.clone());
+            if self.history.len() > MAX_HISTORY {
+                self.history.remove(0);
+            }
+        }
+        line
+    }
+
+    /// Write the history to its file, if it has one.
+    pub fn save_history(&self) -> io::Result<()> {
+        match self.history_file {
+            Some(ref path) => fs::write(path, self.history.join("\n") + "\n"),
+            None => Ok(()),
+        }
+    }
+}
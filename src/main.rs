@@ -1,96 +1,165 @@
-extern crate num;
-
 use num::clamp;
-use std::collections::{HashMap, VecDeque};
+use std::env;
 use std::io::{self, Write};
-use std::sync::mpsc::channel;
+use std::sync::mpsc::{channel, Sender};
 use std::thread;
-use std::time::Instant;
 use termion::cursor::Goto;
 use termion::event::Key;
 use termion::raw::IntoRawMode;
 use tui::backend::TermionBackend;
-use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::layout::{Constraint, Direction, Layout};
 use tui::style::{Color, Style};
 use tui::widgets::canvas::Canvas;
 use tui::widgets::{Block, Borders, Gauge, Paragraph, Text, Widget};
 use tui::Terminal;
-use unicode_width::UnicodeWidthStr;
-
-#[macro_use]
-extern crate strum_macros;
+use std::path::PathBuf;
 
-mod action;
-mod commands;
-mod entities;
 mod event;
-mod event_queue;
-mod game_event;
-mod global_handlers;
-mod room;
-mod rooms;
-mod sound;
-mod state;
-mod timer;
-mod utils;
-
-use crate::action::{Action, ActionHandled};
-use crate::commands::try_handle_command;
-use crate::entities::enemy::initialize_enemies;
-use crate::event::{Event, Events};
-use crate::event_queue::EventQueue;
-use crate::game_event::{GameEvent, GameEventType};
-use crate::global_handlers::handle_action;
-use crate::room::{Room, RoomType};
-use crate::rooms::{CorridorRoom, CryobayRoom, Cryocontrol, SlushLobbyRoom};
-use crate::sound::{AudioEvent, Effect};
-
-use crate::state::State;
-use crate::utils::{duration_to_msec_u64, BoxShape};
-
-#[derive(Debug)]
-pub struct App {
-    // The size of the console window.
-    pub size: Rect,
-    // The system event, like rendering stuff in the console.
-    pub log: VecDeque<GameEvent>,
-    // The input in the command box.
-    pub input: String,
-    // The global game state.
-    pub state: State,
-    // The list of rooms.
-    pub rooms: HashMap<RoomType, Box<Room>>,
-    // The action event queue.
-    pub event_queue: EventQueue,
+mod line_editor;
+mod menu;
+mod pause;
+mod replay;
+mod title;
+mod typewriter;
+
+use crate::line_editor::{load_history, LineEditor};
+use crate::pause::{PauseChoice, PauseMenu};
+use crate::replay::{InputSource, Recorder, Replay, ReplayEvent};
+use crate::title::{TitleChoice, TitleMenu};
+use crate::typewriter::Typewriter;
+use fredjam2018::action::Action;
+use fredjam2018::commands::completions;
+use fredjam2018::dialogue::format_conversation;
+use fredjam2018::entities::npc_name;
+use fredjam2018::game_event::GameEventType;
+use fredjam2018::inventory::format_inventory;
+use fredjam2018::oxygen::MAX_OXYGEN;
+use fredjam2018::save::latest_slot;
+use fredjam2018::settings::Settings;
+use fredjam2018::sound::{self, AudioEvent, Effect, Track};
+use fredjam2018::utils::BoxShape;
+use fredjam2018::{App, Game, GameOutput};
+
+fn present(snd_send: &Sender<AudioEvent>, typewriter: &mut Typewriter, output: GameOutput) {
+    typewriter.write(&output.game_events);
+    for audio_event in output.audio_events {
+        snd_send.send(audio_event).unwrap();
+    }
 }
 
-impl App {
-    fn new(state: State) -> Self {
-        App {
-            size: Default::default(),
-            log: Default::default(),
-            input: "".into(),
-            state: state,
-            rooms: Default::default(),
-            event_queue: Default::default(),
-        }
+fn apply_settings(
+    settings: &Settings,
+    app: &mut App,
+    snd_send: &Sender<AudioEvent>,
+    typewriter: &mut Typewriter,
+) -> io::Result<()> {
+    app.autocorrect = settings.autocorrect;
+    typewriter.speed = settings.text_speed;
+    snd_send.send(AudioEvent::Volume(settings.volume)).unwrap();
+    settings.save()
+}
+
+// Recording only helps with bug reports, so a failed write ends the recording
+// rather than the game.
+fn record(recorder: &mut Option<Recorder>, event: &ReplayEvent, app: &mut App) {
+    let result = match *recorder {
+        Some(ref mut recorder) => recorder.record(event),
+        None => return,
+    };
+    if let Err(err) = result {
+        *recorder = None;
+        app.event_queue.schedule_action(Action::Message(
+            format!("Stopped recording the session: {}.", err),
+            GameEventType::Failure,
+        ));
     }
+}
 
-    pub fn try_handle_room_action(&mut self, action: &Action) -> Option<ActionHandled> {
-        let current_room = self.rooms.get_mut(&self.state.current_room)?;
-        match current_room.handle_action(&mut self.state, &mut self.event_queue, action) {
-            ActionHandled::Handled => Some(ActionHandled::Handled),
-            _ => None,
-        }
+// Drop the colors from `lines` when they are turned off in the settings.
+fn themed<'a>(settings: &Settings, lines: Vec<Text<'a>>) -> Vec<Text<'a>> {
+    if settings.colors {
+        return lines;
     }
+    lines
+        .into_iter()
+        .map(|line| match line {
+            Text::Styled(text, _) => Text::Raw(text),
+            raw => raw,
+        })
+        .collect()
+}
+
+fn themed_style(settings: &Settings, style: Style) -> Style {
+    if settings.colors {
+        style
+    } else {
+        Style::default()
+    }
+}
+
+static DEFAULT_RECORDING: &'static str = "last_session.replay";
 
-    pub fn try_handle_command(&mut self, tokens: String) {
-        let actions = try_handle_command(tokens, &self.state);
-        self.event_queue.schedule_actions(actions);
+static HISTORY_FILE: &'static str = "history.txt";
+
+// Where the pause menu saves to.
+static QUICKSAVE_SLOT: &'static str = "quicksave";
+
+struct Args {
+    seed: Option<u64>,
+    record: String,
+    replay: Option<String>,
+}
+
+fn parse_args() -> Args {
+    let mut args = Args {
+        seed: None,
+        record: DEFAULT_RECORDING.into(),
+        replay: None,
+    };
+    let mut argv = env::args().skip(1);
+    while let Some(arg) = argv.next() {
+        match arg.as_str() {
+            "--seed" => args.seed = argv.next().and_then(|seed| seed.parse().ok()),
+            "--record" => args.record = argv.next().unwrap_or(args.record),
+            "--replay" => args.replay = argv.next(),
+            _ => eprintln!("Ignoring unknown argument {}", arg),
+        }
     }
+    args
 }
 
 fn main() -> Result<(), io::Error> {
+    let args = parse_args();
+    let replay = match args.replay {
+        Some(ref path) => Some(Replay::open(path)?),
+        None => None,
+    };
+    let seed = match replay {
+        Some(ref replay) => replay.seed,
+        None => args.seed.unwrap_or_else(rand::random),
+    };
+    // A replay brings its own history and leaves the real one alone.
+    let mut editor = match replay {
+        Some(ref replay) => LineEditor::new(replay.history.clone(), None),
+        None => {
+            let history_file = PathBuf::from(HISTORY_FILE);
+            LineEditor::new(load_history(&history_file), Some(history_file))
+        }
+    };
+    let mut settings = Settings::load();
+    if let Some(ref replay) = replay {
+        settings.autocorrect = replay.autocorrect;
+    }
+    // Recording only helps with bug reports, so play on without it.
+    let mut recorder = match Recorder::create(&args.record, seed, settings.autocorrect, editor.history()) {
+        Ok(recorder) => Some(recorder),
+        Err(err) => {
+            eprintln!("Not recording to {}: {}", args.record, err);
+            None
+        }
+    };
+    let mut input_source = InputSource::new(replay);
+
     let (snd_send, snd_recv) = channel();
 
     snd_send.send(AudioEvent::Effect(Effect::Typing));
@@ -102,26 +171,64 @@ fn main() -> Result<(), io::Error> {
     let backend = TermionBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let events = Events::new();
-    let mut state = State::new();
-    let mut app = App::new(state);
+    snd_send.send(AudioEvent::Volume(settings.volume)).unwrap();
+    snd_send.send(AudioEvent::Track(Track::Intro)).unwrap();
+    let mut typewriter = Typewriter::new(settings.text_speed);
+
+    let mut game = Game::with_seed(seed)?;
+    game.app.autocorrect = settings.autocorrect;
+    let mut title = Some(TitleMenu::new());
+    // Open while the game is paused.
+    let mut pause: Option<PauseMenu> = None;
 
-    app.rooms
-        .insert(RoomType::Cryobay, Box::new(CryobayRoom::new()));
-    app.rooms
-        .insert(RoomType::Cryocontrol, Box::new(Cryocontrol::new()));
-    app.rooms
-        .insert(RoomType::SlushLobby, Box::new(SlushLobbyRoom::new()));
-    app.rooms
-        .insert(RoomType::Corridor, Box::new(CorridorRoom::new()));
+    loop {
+        if let Some(ref mut menu) = title {
+            let size = terminal.size()?;
+            if size != game.app.size {
+                terminal.resize(size)?;
+                game.app.size = size;
+            }
+            terminal.draw(|mut f| {
+                Paragraph::new(themed(&settings, menu.format(&settings, &game.app.endings)).iter())
+                    .block(Block::default().borders(Borders::ALL))
+                    .wrap(true)
+                    .render(&mut f, size);
+            })?;
 
-    app.event_queue
-        .schedule_action(Action::Enter(RoomType::Cryobay));
+            let event = input_source.next();
+            record(&mut recorder, &event, &mut game.app);
+            let choice = match event {
+                ReplayEvent::Key(key) => menu.handle_key(key, &mut settings),
+                ReplayEvent::Tick(_) => None,
+            };
+            match choice {
+                Some(TitleChoice::NewGame) => {
+                    title = None;
+                    present(&snd_send, &mut typewriter, game.new_game());
+                }
+                Some(TitleChoice::Load(slot)) => {
+                    title = None;
+                    // Should loading fail, the player is left in a new game.
+                    present(&snd_send, &mut typewriter, game.new_game());
+                    typewriter.finish();
+                    present(&snd_send, &mut typewriter, game.load(&slot));
+                }
+                Some(TitleChoice::Settings) => {
+                    // The settings still apply to this session if they can't be kept.
+                    menu.status = apply_settings(&settings, &mut game.app, &snd_send, &mut typewriter)
+                        .err()
+                        .map(|err| format!("Could not save the settings: {}.", err));
+                }
+                Some(TitleChoice::Quit) => break,
+                None => {}
+            }
+            continue;
+        }
 
-    let mut now = Instant::now();
-    initialize_enemies(&mut app.state);
+        present(&snd_send, &mut typewriter, game.update());
+        let can_load = game.is_over() && latest_slot().is_some();
+        let app = &mut game.app;
 
-    loop {
         let size = terminal.size()?;
         if size != app.size {
             terminal.resize(size)?;
@@ -130,15 +237,44 @@ fn main() -> Result<(), io::Error> {
 
         // Draw.
         terminal.draw(|mut f| {
+            if let Some(ref game_over) = app.state.game_over {
+                Paragraph::new(themed(&settings, game_over.format(can_load)).iter())
+                    .block(Block::default().borders(Borders::ALL))
+                    .wrap(true)
+                    .render(&mut f, size);
+                return;
+            }
+            if let Some(ref menu) = pause {
+                Paragraph::new(themed(&settings, menu.format(&settings)).iter())
+                    .block(Block::default().borders(Borders::ALL))
+                    .wrap(true)
+                    .render(&mut f, size);
+                return;
+            }
+
             let h_chunks = Layout::default()
                 // Split along the horizontal axis.
                 .direction(Direction::Horizontal)
                 .margin(1)
                 .constraints([Constraint::Percentage(70), Constraint::Percentage(30)].as_ref())
                 .split(size);
+            // While talking, the answers on offer sit below the log.
+            let conversation = format_conversation(app);
+            let conversation_height = match conversation.len() {
+                0 => 0,
+                // Plus the borders.
+                lines => lines as u16 + 2,
+            };
             let v_chunks_left = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Length(3), Constraint::Min(1)].as_ref())
+                .constraints(
+                    [
+                        Constraint::Length(3),
+                        Constraint::Min(1),
+                        Constraint::Length(conversation_height),
+                    ]
+                    .as_ref(),
+                )
                 .split(h_chunks[0]);
             let input_status_line = Layout::default()
                 .direction(Direction::Horizontal)
@@ -146,7 +282,15 @@ fn main() -> Result<(), io::Error> {
                 .split(v_chunks_left[0]);
             let v_chunks_right = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Percentage(25),
+                        Constraint::Percentage(15),
+                        Constraint::Percentage(30),
+                    ]
+                    .as_ref(),
+                )
                 .split(h_chunks[1]);
             let v_chunks_right_up = Layout::default()
                 .direction(Direction::Vertical)
@@ -165,22 +309,23 @@ fn main() -> Result<(), io::Error> {
 
             let styled_log = {
                 let mut log = vec![];
-                for game_event in &app.log {
+                for (game_event, content) in typewriter.visible(&app.log) {
                     let style = match game_event.game_event_type {
                         GameEventType::Combat => Style::default().fg(Color::Red),
                         GameEventType::Normal => Style::default(),
                         GameEventType::Success => Style::default().fg(Color::Green),
                         GameEventType::Failure => Style::default().fg(Color::Red),
                         GameEventType::Debug => Style::default().fg(Color::Blue),
+                        GameEventType::Ai => Style::default().fg(Color::Cyan),
                     };
-                    log.push(Text::styled(game_event.content.clone(), style));
+                    log.push(Text::styled(content, style));
                 }
-                log
+                themed(&settings, log)
             };
 
             if cfg!(debug_assertions) {
                 Paragraph::new([Text::raw("DEV MODE: no movement restrictions + other cheats enabled")].iter())
-                    .style(Style::default().fg(Color::Red))
+                    .style(themed_style(&settings, Style::default().fg(Color::Red)))
                     .render(&mut f, *v_chunks_right_up.last().unwrap())
             }
 
@@ -188,97 +333,62 @@ fn main() -> Result<(), io::Error> {
                 .block(Block::default().borders(Borders::ALL).title("Events"))
                 .wrap(true)
                 .render(&mut f, v_chunks_left[1]);
-            Paragraph::new([Text::raw(&app.input)].iter())
-                .style(Style::default().fg(Color::Yellow))
-                .block(Block::default().borders(Borders::ALL).title("Input"))
+            if let Some(ref talking) = app.state.conversation {
+                let title = format!("Talking to {}", npc_name(app, talking.npc));
+                Paragraph::new(themed(&settings, conversation).iter())
+                    .block(Block::default().borders(Borders::ALL).title(&title))
+                    .wrap(true)
+                    .render(&mut f, v_chunks_left[2]);
+            }
+            let input_title = if editor.completions.is_empty() {
+                String::from("Input")
+            } else {
+                format!("Input: {}", editor.completions.join(", "))
+            };
+            Paragraph::new([Text::raw(editor.text())].iter())
+                .style(themed_style(&settings, Style::default().fg(Color::Yellow)))
+                .block(Block::default().borders(Borders::ALL).title(&input_title))
                 .render(&mut f, input_status_line[0]);
-            Paragraph::new(app.state.player.format_player_info().iter())
+            Paragraph::new(themed(&settings, app.state.player.format_player_info()).iter())
                 .style(Style::default())
-                .block(Block::default().borders(Borders::ALL).title("Character"))
+                .block(Block::default().borders(Borders::ALL).title(app.state.player.name.as_ref().map_or("Character", String::as_str)))
                 .render(&mut f, input_status_line[1]);
 
+            Paragraph::new(themed(&settings, format_inventory(app)).iter())
+                .block(Block::default().borders(Borders::ALL).title("Inventory"))
+                .wrap(true)
+                .render(&mut f, v_chunks_right[1]);
+            Paragraph::new(themed(&settings, app.state.player.format_status_effects()).iter())
+                .block(Block::default().borders(Borders::ALL).title("Status"))
+                .wrap(true)
+                .render(&mut f, v_chunks_right[2]);
+
             Canvas::default()
                 .block(Block::default().borders(Borders::ALL).title("Map"))
                 .paint(|ctx| {
-                    ctx.draw(&BoxShape {
-                        rect: Rect {
-                            x: 20,
-                            y: 70,
-                            width: 20,
-                            height: 20,
-                        },
-                        color: match app.state.current_room {
-                            RoomType::Cryobay => Color::Red,
-                            _ => Color::White,
-                        },
-                    });
-                    ctx.draw(&BoxShape {
-                        rect: Rect {
-                            x: 30,
-                            y: 60,
-                            width: 5,
-                            height: 10,
-                        },
-                        color: Color::White,
-                    });
-                    ctx.draw(&BoxShape {
-                        rect: Rect {
-                            x: 20,
-                            y: 40,
-                            width: 20,
-                            height: 20,
-                        },
-                        color: match app.state.current_room {
-                            RoomType::SlushLobby => Color::Red,
-                            _ => Color::White,
-                        },
-                    });
-                    ctx.draw(&BoxShape {
-                        rect: Rect {
-                            x: 40,
-                            y: 45,
-                            width: 10,
-                            height: 5,
-                        },
-                        color: Color::White,
-                    });
-                    ctx.draw(&BoxShape {
-                        rect: Rect {
-                            x: 50,
-                            y: 40,
-                            width: 20,
-                            height: 20,
-                        },
-                        color: match app.state.current_room {
-                            RoomType::Cryocontrol => Color::Red,
-                            _ => Color::White,
-                        },
-                    });
-                    ctx.draw(&BoxShape {
-                        rect: Rect {
-                            x: 24,
-                            y: 17,
-                            width: 2,
-                            height: 22,
-                        },
-                        color: Color::White,
-                    });
-                    ctx.draw(&BoxShape {
-                        rect: Rect {
-                            x: 20,
-                            y: 5,
-                            width: 35,
-                            height: 12,
-                        },
-                        color: match app.state.current_room {
-                            RoomType::Corridor => Color::Blue,
-                            _ => Color::White,
-                        },
-                    });
+                    for door in &app.doors {
+                        for passage in &door.definition.map {
+                            ctx.draw(&BoxShape {
+                                rect: passage.to_rect(),
+                                color: Color::White,
+                            });
+                        }
+                    }
+                    for (room_type, room) in &app.rooms {
+                        let definition = room.definition();
+                        ctx.draw(&BoxShape {
+                            rect: definition.map.to_rect(),
+                            color: if *room_type == app.state.current_room {
+                                Color::Red
+                            } else {
+                                Color::White
+                            },
+                        });
+                    }
                 })
                 .x_bounds([0.0, 100.0])
                 .y_bounds([0.0, 100.0])
-                .render(&mut f, v_chunks_right[1]);
+                .render(&mut f, v_chunks_right[3]);
 
             let visible_timers = app
                 .event_queue
@@ -286,8 +396,18 @@ fn main() -> Result<(), io::Error> {
                 .iter()
                 .filter(|timer| timer.is_visual);
 
+            let oxygen = clamp(app.state.player.oxygen, 0, MAX_OXYGEN) as u16;
+            Gauge::default()
+                .block(Block::default().title("Oxygen").borders(Borders::ALL))
+                .style(Style::default().fg(Color::Cyan).bg(Color::Black))
+                .percent(oxygen)
+                .label(&format!("{}", oxygen))
+                .render(&mut f, v_chunks_right_up[0]);
+
+            // The oxygen gauge comes first, timers fill the remaining slots.
             for (index, timer) in visible_timers.enumerate() {
-                // Only render the first 5 timers.
+                let index = index + 1;
+                // Only render the first 5 gauges.
                 if index > 4 {
                     break;
                 }
@@ -309,62 +429,105 @@ fn main() -> Result<(), io::Error> {
         write!(
             terminal.backend_mut(),
             "{}",
-            Goto(3 + app.input.width() as u16, 3)
+            Goto(3 + editor.cursor_column() as u16, 3)
         )?;
 
         terminal.backend_mut().flush()?;
 
         // Handle system events.
-        match events.next().unwrap() {
-            Event::Input(input) => match input {
+        let event = input_source.next();
+        record(&mut recorder, &event, &mut game.app);
+        // Any key skips ahead to the end of the text being typed.
+        if let ReplayEvent::Key(_) = event {
+            typewriter.finish();
+        }
+        if game.is_over() {
+            match event {
+                ReplayEvent::Key(Key::Esc) => break,
+                ReplayEvent::Key(Key::Char('1')) => {
+                    present(&snd_send, &mut typewriter, game.restart()?)
+                }
+                ReplayEvent::Key(Key::Char('2')) => {
+                    present(&snd_send, &mut typewriter, game.load_last_save())
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        // Nothing ticks while paused, so timers and enemies wait too.
+        if let Some(ref mut menu) = pause {
+            let choice = match event {
+                ReplayEvent::Key(key) => menu.handle_key(key, &mut settings),
+                ReplayEvent::Tick(_) => None,
+            };
+            match choice {
+                Some(PauseChoice::Resume) => pause = None,
+                Some(PauseChoice::Save) => {
+                    let output = game.save(QUICKSAVE_SLOT);
+                    menu.status = output
+                        .game_events
+                        .last()
+                        .map(|game_event| game_event.content.clone());
+                    present(&snd_send, &mut typewriter, output);
+                }
+                Some(PauseChoice::Settings) => {
+                    menu.status = apply_settings(&settings, &mut game.app, &snd_send, &mut typewriter)
+                        .err()
+                        .map(|err| format!("Could not save the settings: {}.", err));
+                }
+                Some(PauseChoice::Quit) => break,
+                None => {}
+            }
+            continue;
+        }
+
+        match event {
+            ReplayEvent::Key(input) => match input {
                 Key::Esc => {
-                    break;
+                    pause = Some(PauseMenu::new());
                 }
                 Key::Char('\n') => {
-                    if !app.input.is_empty() {
-                        let mut content: String = app.input.drain(..).collect();
-                        let command = Action::Command(content.clone());
-                        content = format!("\n>>> {}", content);
-                        content.push_str("\n\n");
-                        app.log.push_front(GameEvent {
-                            content: content,
-                            game_event_type: GameEventType::Normal,
-                        });
-                        app.event_queue.schedule_action(command);
+                    if !editor.is_empty() {
+                        let content = editor.submit();
+                        // Losing the history is no reason to stop playing.
+                        if let Err(err) = editor.save_history() {
+                            game.app.event_queue.schedule_action(Action::Message(
+                                format!("Could not save the command history: {}.", err),
+                                GameEventType::Failure,
+                            ));
+                        }
+                        present(&snd_send, &mut typewriter, game.input_line(content.trim()));
                     }
                 }
+                Key::Char('\t') => editor.complete(completions(&game.app, editor.text())),
                 Key::Char(c) => {
                     snd_send.send(AudioEvent::Effect(Effect::Typing));
-                    if app.input.is_empty() {
-                        app.event_queue.schedule_action(Action::PlayerFinishedReading)
+                    let started_typing = editor.is_empty();
+                    editor.insert(c);
+                    if started_typing {
+                        present(&snd_send, &mut typewriter, game.start_input());
                     }
-                    app.input.push(c);
                 }
                 Key::Backspace => {
                     snd_send.send(AudioEvent::Effect(Effect::Backspace));
-                    app.input.pop();
+                    editor.backspace();
                 }
+                Key::Delete => editor.delete(),
+                Key::Ctrl('w') => editor.delete_word(),
+                Key::Left => editor.left(),
+                Key::Right => editor.right(),
+                Key::Home => editor.home(),
+                Key::End => editor.end(),
+                Key::Up => editor.previous(),
+                Key::Down => editor.next(),
                 _ => {}
             },
-            event::Event::Tick => {
-                let elapsed = duration_to_msec_u64(&now.elapsed());
-                app.event_queue.schedule_action(Action::Tick(elapsed));
+            ReplayEvent::Tick(elapsed) => {
+                typewriter.tick(elapsed);
+                present(&snd_send, &mut typewriter, game.tick(elapsed));
             }
         }
-
-        while let Some(next_action) = app.event_queue.get_next_action() {
-            match next_action {
-                Action::Audio(action) => {
-                    snd_send.send(action).unwrap();
-                    continue;
-                },
-                _ => (),
-            }
-
-            handle_action(&mut app, next_action);
-        }
-
-        now = Instant::now();
     }
     Ok(())
 }
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+
+use crate::action::Action;
+use crate::game_event::GameEventType;
+use crate::trigger::Effect;
+use crate::utils::{load_ron, message};
+use crate::App;
+
+/// Something the player remembers from before waking up, as written down in
+/// `assets/memories.ron`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Memory {
+    pub title: String,
+    pub text: String,
+}
+
+pub type Memories = HashMap<String, Memory>;
+
+pub fn load_memories(path: &Path) -> io::Result<Memories> {
+    load_ron(path)
+}
+
+/// Catch memories that triggers and dialogue hand out but don't exist.
+pub fn check_memories(app: &App) -> io::Result<()> {
+    let in_dialogue = app
+        .npcs
+        .values()
+        .flat_map(|definition| definition.tree.effects());
+    let in_effects = app
+        .triggers
+        .iter()
+        .flat_map(|trigger| &trigger.effects)
+        .chain(in_dialogue)
+        .filter_map(|effect| match effect {
+            Effect::Schedule(Action::Remember(id)) => Some(id.as_str()),
+            _ => None,
+        });
+    for id in in_effects {
+        if !app.memories.contains_key(id) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("unknown memory {}", id),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Give the player a memory back. Nothing happens if it is already recovered.
+pub fn remember(app: &mut App, id: &str) {
+    if app.state.player.memories.iter().any(|memory| memory == id) {
+        return;
+    }
+    let memory = match app.memories.get(id) {
+        Some(memory) => memory.clone(),
+        None => return message(app, format!("Unknown memory {}", id), GameEventType::Debug),
+    };
+    app.state.player.memories.push(id.to_string());
+    message(
+        app,
+        format!("A memory comes back to you: {}.", memory.title),
+        GameEventType::Success,
+    );
+    message(app, memory.text, GameEventType::Normal);
+}
+
+/// List the recovered memories, in the order they came back.
+pub fn show_memories(app: &mut App) {
+    if app.state.player.memories.is_empty() {
+        let text = String::from("You can't remember anything from before you woke up. Not yet.");
+        return message(app, text, GameEventType::Normal);
+    }
+    let mut text = format!(
+        "You remember ({} of {}):",
+        app.state.player.memories.len(),
+        app.memories.len()
+    );
+    for id in &app.state.player.memories {
+        if let Some(memory) = app.memories.get(id) {
+            text += &format!("\n- {}: {}", memory.title, memory.text);
+        }
+    }
+    message(app, text, GameEventType::Normal);
+}
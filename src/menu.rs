@@ -0,0 +1,57 @@
+use tui::style::{Color, Modifier, Style};
+use tui::widgets::Text;
+
+use fredjam2018::settings::{Settings, MAX_VOLUME};
+
+// Volume, text speed, colors, autocorrect and the way back.
+pub const SETTINGS_ENTRIES: usize = 5;
+
+pub fn settings_entries(settings: &Settings) -> Vec<String> {
+    vec![
+        format!(
+            "Volume      < {}{} >",
+            "#".repeat(settings.volume as usize),
+            "-".repeat((MAX_VOLUME - settings.volume) as usize)
+        ),
+        format!("Text speed  < {} >", settings.text_speed),
+        format!("Colors      < {} >", if settings.colors { "on" } else { "off" }),
+        format!("Autocorrect < {} >", if settings.autocorrect { "on" } else { "off" }),
+        String::from("Back"),
+    ]
+}
+
+/// Turn the setting at `selected` up or down. Returns whether anything changed.
+pub fn change_setting(selected: usize, increase: bool, settings: &mut Settings) -> bool {
+    match selected {
+        0 if increase => settings.volume = (settings.volume + 1).min(MAX_VOLUME),
+        0 => settings.volume = settings.volume.saturating_sub(1),
+        1 if increase => settings.text_speed = settings.text_speed.faster(),
+        1 => settings.text_speed = settings.text_speed.slower(),
+        2 => settings.colors = !settings.colors,
+        3 => settings.autocorrect = !settings.autocorrect,
+        _ => return false,
+    }
+    true
+}
+
+/// One line per entry, with the selected one highlighted.
+pub fn format_entries<F>(entries: Vec<String>, selected: usize, is_disabled: F) -> Vec<Text<'static>>
+where
+    F: Fn(usize) -> bool,
+{
+    let highlight = Style::default().fg(Color::Yellow).modifier(Modifier::Bold);
+    let disabled = Style::default().fg(Color::DarkGray);
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            if index == selected {
+                Text::styled(format!("> {}\n", entry), highlight)
+            } else if is_disabled(index) {
+                Text::styled(format!("  {}\n", entry), disabled)
+            } else {
+                Text::raw(format!("  {}\n", entry))
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+use crate::entities::{item_name, Item, ItemLocation, StatusEffectType};
+use crate::game_event::GameEventType;
+use crate::game_over::GameOverCause;
+use crate::timer::{Timer, TimerType};
+use crate::{Action, App};
+
+pub const MAX_OXYGEN: i32 = 100;
+
+// The player breathes once per interval, using up the room's `oxygen` rate.
+const OXYGEN_INTERVAL: u64 = 5 * 1000;
+
+// Below this the player starts suffering from low oxygen.
+const LOW_OXYGEN: i32 = 25;
+
+pub fn oxygen_timer() -> Timer {
+    Timer::new(
+        TimerType::Oxygen,
+        // Unused, the oxygen level has its own gauge.
+        "",
+        0,
+        OXYGEN_INTERVAL,
+        Action::Breathe,
+        false,
+    )
+}
+
+pub fn breathe(app: &mut App) {
+    app.event_queue.schedule_timer(oxygen_timer());
+
+    let rate = app.rooms[&app.state.current_room].definition().oxygen;
+    let player = &mut app.state.player;
+    let had_oxygen = player.oxygen > 0;
+    player.oxygen = (player.oxygen + rate).max(0).min(MAX_OXYGEN);
+
+    if player.oxygen < LOW_OXYGEN {
+        // Refreshed on every breath until the air is good again.
+        player.add_status_effect(StatusEffectType::LowOxygen, OXYGEN_INTERVAL * 2);
+    }
+    if had_oxygen && player.oxygen == 0 {
+        app.event_queue.schedule_action(Action::Message(
+            String::from("Your lungs burn. You gasp for air that isn't there."),
+            GameEventType::Failure,
+        ));
+        app.event_queue
+            .schedule_action(Action::PlayerDied(GameOverCause::Suffocated));
+    }
+}
+
+/// Breathe from `item` if it holds oxygen. It is used up in the process.
+pub fn use_oxygen_item(app: &mut App, item: Item) -> bool {
+    let amount = match app.items.get(&item).and_then(|definition| definition.oxygen) {
+        Some(amount) if app.state.has_item(item) => amount,
+        _ => return false,
+    };
+
+    app.state.item_locations.insert(item, ItemLocation::Nowhere);
+    let player = &mut app.state.player;
+    player.oxygen = (player.oxygen + amount).min(MAX_OXYGEN);
+    app.event_queue.schedule_action(Action::Message(
+        format!(
+            "You take a few deep breaths from the {}. Oxygen at {}%.",
+            item_name(&app.items, item),
+            player.oxygen
+        ),
+        GameEventType::Success,
+    ));
+    true
+}
@@ -0,0 +1,139 @@
+use termion::event::Key;
+use tui::style::{Color, Modifier, Style};
+use tui::widgets::Text;
+
+use crate::menu::{change_setting, format_entries, settings_entries, SETTINGS_ENTRIES};
+use fredjam2018::help::command_list;
+use fredjam2018::settings::Settings;
+
+/// What the player picked in the pause menu.
+pub enum PauseChoice {
+    Resume,
+    Save,
+    // A setting was changed and should be applied and saved.
+    Settings,
+    Quit,
+}
+
+enum Page {
+    Main,
+    Settings,
+    Help,
+    ConfirmQuit,
+}
+
+static MAIN_ENTRIES: [&'static str; 5] = ["Resume", "Save", "Settings", "Help", "Quit"];
+
+static CONFIRM_QUIT_ENTRIES: [&'static str; 2] = ["No, keep playing", "Yes, quit"];
+
+static CONTROLS: &'static str = "Type a command and press Enter. Left, Right, Home and \
+                                 End move the cursor, Ctrl-W deletes a word, Up and Down \
+                                 bring back earlier commands and Tab completes names. Esc \
+                                 opens this menu. Time stands still while it is open.";
+
+pub struct PauseMenu {
+    page: Page,
+    selected: usize,
+    // The outcome of the last save, of the game or the settings, shown below
+    // the entries.
+    pub status: Option<String>,
+}
+
+impl PauseMenu {
+    pub fn new() -> Self {
+        PauseMenu {
+            page: Page::Main,
+            selected: 0,
+            status: None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.page {
+            Page::Main => MAIN_ENTRIES.len(),
+            Page::Settings => SETTINGS_ENTRIES,
+            // Only the way back.
+            Page::Help => 1,
+            Page::ConfirmQuit => CONFIRM_QUIT_ENTRIES.len(),
+        }
+    }
+
+    fn open(&mut self, page: Page) {
+        self.page = page;
+        self.selected = 0;
+    }
+
+    pub fn handle_key(&mut self, key: Key, settings: &mut Settings) -> Option<PauseChoice> {
+        match key {
+            Key::Up => self.selected = (self.selected + self.len() - 1) % self.len(),
+            Key::Down => self.selected = (self.selected + 1) % self.len(),
+            Key::Left | Key::Right => {
+                if let Page::Settings = self.page {
+                    if change_setting(self.selected, key == Key::Right, settings) {
+                        return Some(PauseChoice::Settings);
+                    }
+                }
+            }
+            Key::Char('\n') => return self.select(settings),
+            Key::Esc => match self.page {
+                Page::Main => return Some(PauseChoice::Resume),
+                _ => self.open(Page::Main),
+            },
+            _ => {}
+        }
+        None
+    }
+
+    fn select(&mut self, settings: &mut Settings) -> Option<PauseChoice> {
+        match self.page {
+            Page::Main => match self.selected {
+                0 => return Some(PauseChoice::Resume),
+                1 => return Some(PauseChoice::Save),
+                2 => self.open(Page::Settings),
+                3 => self.open(Page::Help),
+                _ => self.open(Page::ConfirmQuit),
+            },
+            Page::Settings => {
+                if self.selected == SETTINGS_ENTRIES - 1 {
+                    self.open(Page::Main);
+                } else if change_setting(self.selected, true, settings) {
+                    return Some(PauseChoice::Settings);
+                }
+            }
+            Page::Help => self.open(Page::Main),
+            Page::ConfirmQuit => match self.selected {
+                0 => self.open(Page::Main),
+                _ => return Some(PauseChoice::Quit),
+            },
+        }
+        None
+    }
+
+    pub fn format(&self, settings: &Settings) -> Vec<Text> {
+        let mut lines = vec![Text::styled(
+            "PAUSED\n\n",
+            Style::default().fg(Color::Cyan).modifier(Modifier::Bold),
+        )];
+        let entries = match self.page {
+            Page::Main => MAIN_ENTRIES.iter().map(|entry| entry.to_string()).collect(),
+            Page::Settings => settings_entries(settings),
+            Page::Help => {
+                lines.push(Text::raw(format!("{}\n\n{}\n\n", command_list(), CONTROLS)));
+                vec![String::from("Back")]
+            }
+            Page::ConfirmQuit => {
+                lines.push(Text::styled(
+                    "Quit the game? Anything you have not saved will be lost.\n\n",
+                    Style::default().fg(Color::Red),
+                ));
+                CONFIRM_QUIT_ENTRIES.iter().map(|entry| entry.to_string()).collect()
+            }
+        };
+        lines.extend(format_entries(entries, self.selected, |_| false));
+
+        if let Some(ref status) = self.status {
+            lines.push(Text::raw(format!("\n{}", status)));
+        }
+        lines
+    }
+}
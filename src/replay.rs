@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use termion::event::Key;
+
+use crate::event::{Event, Events};
+use fredjam2018::utils::duration_to_msec_u64;
+
+/// Everything the main loop reacts to, in a form that can be written to disk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplayEvent {
+    Key(Key),
+    Tick(u64),
+}
+
+fn invalid_data(line: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid replay line: {}", line),
+    )
+}
+
+fn parse_char(code: &str) -> Option<char> {
+    code.parse().ok().and_then(std::char::from_u32)
+}
+
+impl ReplayEvent {
+    // Chars are stored as code points so whitespace and newlines survive.
+    fn to_line(&self) -> String {
+        match self {
+            ReplayEvent::Tick(dt) => format!("tick {}", dt),
+            ReplayEvent::Key(key) => match key {
+                Key::Char(c) => format!("key char {}", *c as u32),
+                Key::Alt(c) => format!("key alt {}", *c as u32),
+                Key::Ctrl(c) => format!("key ctrl {}", *c as u32),
+                Key::F(n) => format!("key f {}", n),
+                Key::Backspace => "key backspace".into(),
+                Key::Left => "key left".into(),
+                Key::Right => "key right".into(),
+                Key::Up => "key up".into(),
+                Key::Down => "key down".into(),
+                Key::Home => "key home".into(),
+                Key::End => "key end".into(),
+                Key::PageUp => "key pageup".into(),
+                Key::PageDown => "key pagedown".into(),
+                Key::Delete => "key delete".into(),
+                Key::Insert => "key insert".into(),
+                Key::Esc => "key esc".into(),
+                _ => "key null".into(),
+            },
+        }
+    }
+
+    fn from_line(line: &str) -> Option<ReplayEvent> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let key = match parts.as_slice() {
+            ["tick", dt] => return dt.parse().ok().map(ReplayEvent::Tick),
+            ["key", "char", code] => Key::Char(parse_char(code)?),
+            ["key", "alt", code] => Key::Alt(parse_char(code)?),
+            ["key", "ctrl", code] => Key::Ctrl(parse_char(code)?),
+            ["key", "f", n] => Key::F(n.parse().ok()?),
+            ["key", "backspace"] => Key::Backspace,
+            ["key", "left"] => Key::Left,
+            ["key", "right"] => Key::Right,
+            ["key", "up"] => Key::Up,
+            ["key", "down"] => Key::Down,
+            ["key", "home"] => Key::Home,
+            ["key", "end"] => Key::End,
+            ["key", "pageup"] => Key::PageUp,
+            ["key", "pagedown"] => Key::PageDown,
+            ["key", "delete"] => Key::Delete,
+            ["key", "insert"] => Key::Insert,
+            ["key", "esc"] => Key::Esc,
+            ["key", "null"] => Key::Null,
+            _ => return None,
+        };
+        Some(ReplayEvent::Key(key))
+    }
+}
+
+/// Writes a session to a replay file: the seed first, then whether
+/// autocorrect was on and the command history the session started with,
+/// then one event per line.
+pub struct Recorder {
+    out: BufWriter<File>,
+}
+
+impl Recorder {
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        seed: u64,
+        autocorrect: bool,
+        history: &[String],
+    ) -> io::Result<Recorder> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "seed {}", seed)?;
+        if autocorrect {
+            writeln!(out, "autocorrect")?;
+        }
+        for line in history {
+            writeln!(out, "history {}", line)?;
+        }
+        Ok(Recorder { out })
+    }
+
+    pub fn record(&mut self, event: &ReplayEvent) -> io::Result<()> {
+        writeln!(self.out, "{}", event.to_line())?;
+        // Flush right away so a crash still leaves a complete replay behind.
+        self.out.flush()
+    }
+}
+
+/// A recorded session read back from disk.
+pub struct Replay {
+    pub seed: u64,
+    // Typos change what commands do, so the setting has to match.
+    pub autocorrect: bool,
+    // So browsing the history brings up the same commands as when recording.
+    pub history: Vec<String>,
+    pub events: VecDeque<ReplayEvent>,
+}
+
+impl Replay {
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Replay> {
+        let mut lines = BufReader::new(File::open(path)?).lines();
+
+        let header = lines.next().unwrap_or_else(|| Ok(String::new()))?;
+        let seed = match header.split_whitespace().collect::<Vec<_>>().as_slice() {
+            ["seed", seed] => seed.parse().map_err(|_| invalid_data(&header))?,
+            _ => return Err(invalid_data(&header)),
+        };
+
+        let mut autocorrect = false;
+        let mut history = vec![];
+        let mut events = VecDeque::new();
+        for line in lines {
+            let line = line?;
+            if events.is_empty() && line == "autocorrect" {
+                autocorrect = true;
+                continue;
+            }
+            if events.is_empty() && line.starts_with("history ") {
+                history.push(line["history ".len()..].to_string());
+                continue;
+            }
+            events.push_back(ReplayEvent::from_line(&line).ok_or_else(|| invalid_data(&line))?);
+        }
+        Ok(Replay {
+            seed,
+            autocorrect,
+            history,
+            events,
+        })
+    }
+}
+
+/// Feeds the main loop, first from a replay if there is one, then from the
+/// terminal. Replayed ticks are paced in real time so they can be watched.
+pub struct InputSource {
+    replay: VecDeque<ReplayEvent>,
+    events: Option<Events>,
+    last_event: Instant,
+}
+
+impl InputSource {
+    pub fn new(replay: Option<Replay>) -> InputSource {
+        InputSource {
+            replay: replay.map(|replay| replay.events).unwrap_or_default(),
+            events: None,
+            last_event: Instant::now(),
+        }
+    }
+
+    pub fn next(&mut self) -> ReplayEvent {
+        if let Some(event) = self.replay.pop_front() {
+            if let ReplayEvent::Tick(dt) = event {
+                thread::sleep(Duration::from_millis(dt));
+            }
+            self.last_event = Instant::now();
+            return event;
+        }
+
+        // Only start reading the terminal once the replay has run out.
+        let events = self.events.get_or_insert_with(Events::new);
+        let event = match events.next().unwrap() {
+            Event::Input(key) => ReplayEvent::Key(key),
+            Event::Tick => ReplayEvent::Tick(duration_to_msec_u64(&self.last_event.elapsed())),
+        };
+        self.last_event = Instant::now();
+        event
+    }
+}
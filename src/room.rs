@@ -1,129 +1,218 @@
-use std::fmt::Debug;
-
-use strum::EnumProperty;
-
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use serde_json::Value;
+use tui::layout::Rect;
+
+use crate::ai::narrate;
+use crate::dialogue::end_conversation;
+use crate::door::Door;
+use crate::entities::enemy::create_enemy;
+use crate::entities::{describe_npcs_in_room, move_followers};
+use crate::fixture::Fixture;
 use crate::game_event::{GameEvent, GameEventType};
+use crate::script::{run_hook, run_room_hook};
 use crate::sound::{AudioEvent, Track};
+use crate::trigger::run_triggers;
+use crate::utils::{intern, invalid_data, load_ron};
 use crate::App;
-use crate::EventQueue;
-use crate::{Action, ActionHandled, State};
+use crate::{Action, ActionHandled};
 
 pub trait Room: Debug {
-    fn handle_action(
-        &mut self,
-        state: &mut State,
-        event_queue: &mut EventQueue,
-        action: &Action,
-    ) -> ActionHandled;
-
-    fn is_opened(&self) -> bool;
-    fn open(&mut self);
+    fn definition(&self) -> &RoomDefinition;
+
     fn visit(&mut self);
     fn is_visited(&self) -> bool;
-}
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, EnumProperty)]
-pub enum RoomType {
-    #[strum(props(game_name = "cryobay"))]
-    Cryobay,
-    #[strum(props(game_name = "slush lobby"))]
-    SlushLobby,
-    #[strum(props(game_name = "cryocontrol"))]
-    Cryocontrol,
-    #[strum(props(game_name = "ventilation shaft"))]
-    Corridor,
+    // Snapshot of the room's flags for save games.
+    fn save(&self) -> Value;
+    // A copy of the room as it was when `value` was saved.
+    fn load(&self, value: Value) -> Result<Box<Room>, serde_json::Error>;
 }
 
+pub type Rooms = HashMap<RoomType, Box<Room>>;
+
+/// Identifies a room by the file name of its definition in `assets/rooms/`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub struct RoomType(&'static str);
+
+// The room every new game starts in.
+pub const START_ROOM: RoomType = RoomType("cryobay");
+
 impl RoomType {
-    fn get_track(&self) -> Track {
-        match self {
-            RoomType::Cryobay => Track::Intro,
-            RoomType::Cryocontrol => Track::Complications,
-            _ => Track::Loop,
-        }
+    pub fn id(&self) -> &'static str {
+        self.0
     }
 }
 
-pub fn room_intro_text(room_type: RoomType) -> (&'static str, &'static str) {
-    match room_type {
-        RoomType::Cryobay => (
-            include_str!("../assets/rooms/cryobay_enter.txt"),
-            include_str!("../assets/rooms/cryobay_enter_first.txt"),
-        ),
-        RoomType::SlushLobby => (
-            include_str!("../assets/rooms/slush_lobby_enter.txt"),
-            include_str!("../assets/rooms/slush_lobby_enter_first.txt"),
-        ),
-        RoomType::Cryocontrol => (
-            include_str!("../assets/rooms/cryocontrol_enter.txt"),
-            include_str!("../assets/rooms/cryocontrol_enter_first.txt"),
-        ),
-        RoomType::Corridor => (
-            include_str!("../assets/rooms/corridor_enter.txt"),
-            include_str!("../assets/rooms/corridor_enter_first.txt"),
-        ),
+impl<'a> From<&'a str> for RoomType {
+    fn from(id: &'a str) -> Self {
+        RoomType(intern(id))
     }
 }
 
-pub fn reading_time_msecs(room_type: RoomType, has_visited: bool) -> u64 {
-    let intros = room_intro_text(room_type);
-    let msg = if has_visited { intros.0 } else { intros.1 };
-    // 0.05 of a second for every character, times 1000 to convert to msecs
-    (msg.len() as f64 * 0.05 * 1000.0).floor() as u64
+impl fmt::Display for RoomType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
-pub fn adjacent_rooms(room_type: RoomType) -> Vec<RoomType> {
-    match room_type {
-        RoomType::Cryobay => vec![RoomType::SlushLobby],
-        RoomType::SlushLobby => vec![RoomType::Cryobay, RoomType::Cryocontrol, RoomType::Corridor],
-        RoomType::Cryocontrol => vec![RoomType::SlushLobby],
-        RoomType::Corridor => vec![RoomType::SlushLobby],
+impl Serialize for RoomType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
     }
 }
 
-pub fn room_type_from_name(room_name: &str) -> Option<RoomType> {
-    match room_name {
-        "cryobay" => Some(RoomType::Cryobay),
-        "slush lobby" => Some(RoomType::SlushLobby),
-        "cryocontrol" => Some(RoomType::Cryocontrol),
-        "ventilation shaft" => Some(RoomType::Corridor),
-        _ => None,
+impl<'de> Deserialize<'de> for RoomType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(RoomType::from(id.as_str()))
     }
 }
 
-pub fn closed_message(room_type: RoomType) -> String {
-    match room_type {
-        RoomType::Corridor => "Peering through the ventilation shafts, \
-                               it looks like they connect to a corridor. \
-                               You would need a tool to get through."
-            .into(),
-        _ => format!(
-            "The door to {} is closed and won't open.",
-            room_type.get_str("game_name").unwrap()
-        )
-        .into(),
+/// Where a room is drawn on the map canvas, which spans 100x100.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MapShape {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl MapShape {
+    pub fn to_rect(&self) -> Rect {
+        Rect {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
     }
 }
 
-fn change_music(app: &mut App, room_type: RoomType) {
+/// A room as written down in `assets/rooms/<id>.ron`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoomDefinition {
+    // The name the player types and sees on door labels.
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    // Text files next to the definition. Replaced by their contents on load.
+    pub enter_text: String,
+    pub enter_first_text: String,
+    // Name of an enemy template from `entities::enemy::create_enemy`.
+    #[serde(default)]
+    pub enemy: Option<String>,
+    pub track: Track,
+    pub map: MapShape,
+    // Oxygen gained per breath, negative where the air is bad.
+    #[serde(default = "default_oxygen")]
+    pub oxygen: i32,
+    // File name of a script in `assets/scripts/` with hooks for this room.
+    #[serde(default)]
+    pub script: Option<String>,
+    // What can be looked at besides items.
+    #[serde(default)]
+    pub fixtures: Vec<Fixture>,
+}
+
+fn default_oxygen() -> i32 {
+    -1
+}
+
+/// Read every `*.ron` room definition in `dir`, along with its texts.
+pub fn load_room_definitions(dir: &Path) -> io::Result<Vec<(RoomType, RoomDefinition)>> {
+    let mut definitions = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().map_or(true, |extension| extension != "ron") {
+            continue;
+        }
+
+        let id = match path.file_stem().and_then(|stem| stem.to_str()) {
+            Some(id) => RoomType::from(id),
+            None => continue,
+        };
+        let mut definition: RoomDefinition = load_ron(&path)?;
+        // A typo would quietly take the fight out of the room.
+        if let Some(ref enemy) = definition.enemy {
+            if create_enemy(enemy).is_none() {
+                return Err(invalid_data(&path, format!("unknown enemy {}", enemy)));
+            }
+        }
+        definition.enter_text = fs::read_to_string(dir.join(&definition.enter_text))?;
+        definition.enter_first_text = fs::read_to_string(dir.join(&definition.enter_first_text))?;
+        definitions.push((id, definition));
+    }
+    Ok(definitions)
+}
+
+pub fn reading_time_msecs(definition: &RoomDefinition, has_visited: bool) -> u64 {
+    let msg = if has_visited {
+        &definition.enter_text
+    } else {
+        &definition.enter_first_text
+    };
+    // 0.05 of a second for every character, times 1000 to convert to msecs
+    (msg.len() as f64 * 0.05 * 1000.0).floor() as u64
+}
+
+pub fn room_type_from_name(rooms: &Rooms, room_name: &str) -> Option<RoomType> {
+    rooms
+        .iter()
+        .find(|(_, room)| {
+            let definition = room.definition();
+            definition.name.eq_ignore_ascii_case(room_name)
+                || definition.aliases.iter().any(|alias| alias.eq_ignore_ascii_case(room_name))
+        })
+        .map(|(room_type, _)| *room_type)
+}
+
+pub fn room_name(rooms: &Rooms, room_type: RoomType) -> &str {
+    rooms
+        .get(&room_type)
+        .map_or(room_type.id(), |room| room.definition().name.as_str())
+}
+
+pub fn change_music(app: &mut App, room_type: RoomType) {
+    let track = app.rooms.get(&room_type).unwrap().definition().track;
     app.event_queue
-        .schedule_action(Action::Audio(AudioEvent::Track(room_type.get_track())));
+        .schedule_action(Action::Audio(AudioEvent::Track(track)));
 }
 
 pub fn enter_room(app: &mut App, room_type: RoomType) {
+    // `room` still means the room being left here.
+    if let ActionHandled::Handled = run_triggers(app, &Action::Enter(room_type)) {
+        return;
+    }
+    end_conversation(app);
     change_music(app, room_type);
 
+    let previous_room = app.state.current_room;
     app.state.current_room = room_type;
-    let available_rooms = adjacent_rooms(room_type);
-    let plural = if available_rooms.len() > 1 { "s" } else { "" };
+    let available_doors: Vec<&Door> = app
+        .doors
+        .iter()
+        .filter(|door| door.leads_to(room_type).is_some())
+        .collect();
+    let plural = if available_doors.len() > 1 { "s" } else { "" };
     let mut door_msg = format!(
         "\nYou see {} door{} labeled:\n",
-        &available_rooms.len(),
+        &available_doors.len(),
         plural
     );
-    for room in available_rooms {
+    for door in available_doors {
         door_msg += "  - ";
-        door_msg += room.get_str("game_name").unwrap();
+        door_msg += room_name(&app.rooms, door.leads_to(room_type).unwrap());
+        if let Some(ref description) = door.definition.description {
+            door_msg += ", ";
+            door_msg += description;
+        }
         door_msg += "\n";
     }
 
@@ -132,39 +221,31 @@ pub fn enter_room(app: &mut App, room_type: RoomType) {
         game_event_type: GameEventType::Normal,
     });
 
-    app.event_queue
-        .schedule_actions(room_specific_actions(&app, room_type));
-
-    let has_visited = app.rooms.get(&room_type).unwrap().is_visited();
-    if has_visited {
-        app.event_queue.schedule_action(Action::Message(
-            String::from(room_intro_text(room_type).0),
-            GameEventType::Normal,
-        ));
+    let room = app.rooms.get_mut(&room_type).unwrap();
+    let enter_text = if room.is_visited() {
+        room.definition().enter_text.clone()
     } else {
-        app.event_queue.schedule_action(Action::Message(
-            String::from(room_intro_text(room_type).1),
-            GameEventType::Normal,
-        ));
+        room.definition().enter_first_text.clone()
+    };
+    let first_visit = !room.is_visited();
+    room.visit();
+    narrate(app, &enter_text);
+
+    move_followers(app, previous_room);
+    // A first visit's text introduces whoever is there.
+    if !first_visit {
+        describe_npcs_in_room(app);
     }
-    app.rooms.get_mut(&room_type).unwrap().visit();
-}
-
-fn room_specific_actions(app: &App, room_type: RoomType) -> Vec<Action> {
-    match room_type {
-        RoomType::Cryocontrol => {
-            if app.rooms.get(&room_type).unwrap().is_visited()
-                && app.state.get_current_enemy(room_type).is_none()
-            {
-                vec![Action::Message(
-                    "The central cortex rumbles uneasily. There's a terminal in front of it."
-                        .into(),
-                    GameEventType::Normal,
-                )]
-            } else {
-                vec![]
-            }
-        }
-        _ => vec![],
+
+    if previous_room != room_type {
+        run_hook_in(app, previous_room, "on_leave");
+    }
+    run_room_hook(app, "on_enter", 1, (first_visit,));
+}
+
+fn run_hook_in(app: &mut App, room_type: RoomType, hook: &str) {
+    let script = app.rooms[&room_type].definition().script.clone();
+    if let Some(script) = script {
+        run_hook(app, &script, hook, 0, ());
     }
 }
@@ -0,0 +1,50 @@
+use serde_json::Value;
+
+use crate::room::{Room, RoomDefinition};
+
+// The parts of a room that change while playing.
+#[derive(Debug, Serialize, Deserialize)]
+struct RoomProgress {
+    visited: bool,
+}
+
+/// A room driven entirely by its `RoomDefinition`.
+#[derive(Debug)]
+pub struct GenericRoom {
+    definition: RoomDefinition,
+    progress: RoomProgress,
+}
+
+impl GenericRoom {
+    pub fn new(definition: RoomDefinition) -> GenericRoom {
+        GenericRoom {
+            progress: RoomProgress { visited: false },
+            definition,
+        }
+    }
+}
+
+impl Room for GenericRoom {
+    fn definition(&self) -> &RoomDefinition {
+        &self.definition
+    }
+
+    fn visit(&mut self) {
+        self.progress.visited = true;
+    }
+
+    fn is_visited(&self) -> bool {
+        self.progress.visited
+    }
+
+    fn save(&self) -> Value {
+        serde_json::to_value(&self.progress).unwrap()
+    }
+
+    fn load(&self, value: Value) -> Result<Box<Room>, serde_json::Error> {
+        Ok(Box::new(GenericRoom {
+            definition: self.definition.clone(),
+            progress: serde_json::from_value(value)?,
+        }))
+    }
+}
@@ -1,9 +1,3 @@
-pub mod corridor;
-pub mod cryobay;
-pub mod cryocontrol;
-pub mod slush_lobby;
+pub mod generic;
 
-pub use self::corridor::*;
-pub use self::cryobay::*;
-pub use self::cryocontrol::*;
-pub use self::slush_lobby::*;
+pub use self::generic::*;
@@ -0,0 +1,467 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+
+use crate::ai::FULL_INTEGRITY;
+use crate::entities::enemy::initialize_enemies;
+use crate::entities::item::{initialize_items, Item, ItemLocation};
+use crate::entities::npc::{initialize_npcs, npc_timer, Npc, NpcState};
+use crate::entities::player::Player;
+use crate::event_queue::EventQueue;
+use crate::game_event::GameEvent;
+use crate::game_over::RunStats;
+use crate::oxygen::{oxygen_timer, MAX_OXYGEN};
+use crate::room::RoomType;
+use crate::state::State;
+use crate::App;
+
+// Bump this whenever the layout of `SaveGame` changes and append a migration
+// from the previous version to `MIGRATIONS`.
+pub const SAVE_VERSION: u64 = 13;
+
+// `MIGRATIONS[n]` upgrades a save from version `n + 1` to version `n + 2`.
+static MIGRATIONS: &'static [fn(Value) -> Value] = &[add_rng_seed, use_room_ids, move_locks_to_doors, add_flags, track_item_locations, add_status_effects, add_oxygen, add_game_over_causes, add_npcs, add_ai_integrity, lock_bridge, add_memories];
+
+static SAVE_DIR: &'static str = "saves";
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Format(serde_json::Error),
+    InvalidSlot(String),
+    UnsupportedVersion(u64),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "{}", err),
+            SaveError::Format(err) => write!(f, "the save file is corrupt ({})", err),
+            SaveError::InvalidSlot(slot) => write!(
+                f,
+                "\"{}\" is not a valid slot name, use only letters, digits, - and _",
+                slot
+            ),
+            SaveError::UnsupportedVersion(version) => {
+                write!(f, "save version {} is not supported", version)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        SaveError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Format(err)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SaveGame {
+    version: u64,
+    current_room: RoomType,
+    // The RNG is reseeded with this on save, so loading continues identically.
+    rng_seed: u64,
+    player: Player,
+    flags: BTreeSet<String>,
+    items: HashMap<Item, ItemLocation>,
+    npcs: HashMap<Npc, NpcState>,
+    ai_integrity: i32,
+    stats: RunStats,
+    // Remaining health of every living enemy, keyed by the room it guards.
+    enemies: HashMap<RoomType, i32>,
+    rooms: HashMap<RoomType, Value>,
+    // The doors still locked, named by the two rooms they join.
+    locked_doors: Vec<(RoomType, RoomType)>,
+    // Pending actions and timers, including their current `elapsed`.
+    event_queue: EventQueue,
+    log: VecDeque<GameEvent>,
+}
+
+fn slot_path(slot: &str) -> Result<PathBuf, SaveError> {
+    let is_valid = !slot.is_empty()
+        && slot
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !is_valid {
+        return Err(SaveError::InvalidSlot(slot.into()));
+    }
+    Ok(PathBuf::from(SAVE_DIR).join(format!("{}.json", slot)))
+}
+
+// Version 1 predates the seeded RNG.
+fn add_rng_seed(mut save: Value) -> Value {
+    save["rng_seed"] = 0.into();
+    save
+}
+
+// Version 2 named rooms and items after their Rust enum variants and kept
+// hand-written flags per room.
+fn use_room_ids(mut save: Value) -> Value {
+    fn room_id(name: &str) -> Value {
+        match name {
+            "Cryobay" => "cryobay",
+            "SlushLobby" => "slush_lobby",
+            "Cryocontrol" => "cryocontrol",
+            "Corridor" => "corridor",
+            other => other,
+        }
+        .into()
+    }
+
+    fn item_id(name: &str) -> Value {
+        name.to_lowercase().into()
+    }
+
+    fn migrate_action(action: &Value) -> Option<Value> {
+        let migrated = match action {
+            Value::String(name) => match name.as_str() {
+                "PickUpCrowbar" => json!({ "PickUp": "crowbar" }),
+                "PickUpKeycard" => json!({ "PickUp": "keycard" }),
+                "UseCrowbar" => json!({ "UseItem": "crowbar" }),
+                "UseKeycard" => json!({ "UseItem": "keycard" }),
+                "OpenCorridor" | "OpenCryoControl" => return None,
+                _ => action.clone(),
+            },
+            Value::Object(variant) => match variant.iter().next() {
+                Some((name, Value::String(room))) if name == "Enter" || name == "Leave" => {
+                    json!({ name.as_str(): room_id(room) })
+                }
+                Some((name, Value::String(item))) if name == "PickUp" => {
+                    json!({ "PickUp": item_id(item) })
+                }
+                _ => action.clone(),
+            },
+            _ => action.clone(),
+        };
+        Some(migrated)
+    }
+
+    let has_keycard = save["player"]["items"]
+        .as_array()
+        .map_or(false, |items| items.iter().any(|item| item == "KeyCard"));
+    if let Some(items) = save["player"]["items"].as_array_mut() {
+        for item in items.iter_mut() {
+            *item = item_id(item.as_str().unwrap_or_default());
+        }
+    }
+
+    save["current_room"] = room_id(save["current_room"].as_str().unwrap_or_default());
+
+    let enemies = save["enemies"].as_object().cloned().unwrap_or_default();
+    save["enemies"] = enemies
+        .into_iter()
+        .map(|(room, health)| (room_id(&room).as_str().unwrap().to_string(), health))
+        .collect();
+
+    let rooms = save["rooms"].as_object().cloned().unwrap_or_default();
+    save["rooms"] = rooms
+        .into_iter()
+        .map(|(room, flags)| {
+            let mut items = vec![];
+            if flags["crowbar"] == true {
+                items.push("crowbar");
+            }
+            if flags["keycard"] == true && !has_keycard {
+                items.push("keycard");
+            }
+            let progress = json!({
+                "visited": flags["visited"].as_bool().unwrap_or(false),
+                "opened": flags["opened"].as_bool().unwrap_or(true),
+                "items": items,
+            });
+            (room_id(&room).as_str().unwrap().to_string(), progress)
+        })
+        .collect();
+
+    let queue = &mut save["event_queue"];
+    if let Some(actions) = queue["actions"].as_array() {
+        queue["actions"] = actions.iter().filter_map(migrate_action).collect();
+    }
+    if let Some(timers) = queue["timers"].as_array() {
+        queue["timers"] = timers
+            .iter()
+            .filter_map(|timer| {
+                let mut timer = timer.clone();
+                timer["action"] = migrate_action(&timer["action"])?;
+                Some(timer)
+            })
+            .collect();
+    }
+    save
+}
+
+// Version 3 kept an `opened` flag on the locked rooms instead of their doors.
+// Every lockable room back then was reached from the slush lobby.
+fn move_locks_to_doors(mut save: Value) -> Value {
+    let mut locked_doors = vec![];
+    if let Some(rooms) = save["rooms"].as_object_mut() {
+        for (room, progress) in rooms.iter_mut() {
+            let opened = progress
+                .as_object_mut()
+                .and_then(|progress| progress.remove("opened"));
+            if opened == Some(Value::Bool(false)) {
+                locked_doors.push(json!(["slush_lobby", room]));
+            }
+        }
+    }
+    save["locked_doors"] = locked_doors.into();
+    save
+}
+
+// Version 4 predates triggers and their flags.
+fn add_flags(mut save: Value) -> Value {
+    save["flags"] = json!([]);
+    save
+}
+
+// Version 5 kept carried items on the player and the rest in their rooms.
+fn track_item_locations(mut save: Value) -> Value {
+    let mut items = serde_json::Map::new();
+    if let Some(rooms) = save["rooms"].as_object_mut() {
+        for (room, progress) in rooms.iter_mut() {
+            let room_items = progress
+                .as_object_mut()
+                .and_then(|progress| progress.remove("items"));
+            for item in room_items.as_ref().and_then(Value::as_array).into_iter().flatten() {
+                if let Some(item) = item.as_str() {
+                    items.insert(item.to_string(), json!({ "Room": room }));
+                }
+            }
+        }
+    }
+
+    let carried = save["player"]
+        .as_object_mut()
+        .and_then(|player| player.remove("items"));
+    for item in carried.as_ref().and_then(Value::as_array).into_iter().flatten() {
+        if let Some(item) = item.as_str() {
+            items.insert(item.to_string(), json!("Player"));
+        }
+    }
+
+    // Both old items existed from the start, so missing ones were used up.
+    for item in &["crowbar", "keycard"] {
+        items.entry(item.to_string()).or_insert(json!("Nowhere"));
+    }
+    save["items"] = items.into();
+    save
+}
+
+// Version 6 predates status effects.
+fn add_status_effects(mut save: Value) -> Value {
+    save["player"]["status_effects"] = json!([]);
+    save
+}
+
+// Version 7 predates oxygen, so start with full lungs and a fresh timer.
+fn add_oxygen(mut save: Value) -> Value {
+    save["player"]["oxygen"] = MAX_OXYGEN.into();
+    if let Some(timers) = save["event_queue"]["timers"].as_array_mut() {
+        timers.push(serde_json::to_value(oxygen_timer()).unwrap());
+    }
+    save
+}
+
+// Version 8 had no run statistics, and dying or ending the game had no cause.
+fn add_game_over_causes(mut save: Value) -> Value {
+    fn add_cause(action: &mut Value) {
+        if *action == "PlayerDied" {
+            *action = json!({ "PlayerDied": "Killed" });
+        } else if *action == "GameOver" {
+            *action = json!({ "GameOver": "Casket" });
+        }
+    }
+
+    save["stats"] = serde_json::to_value(RunStats::default()).unwrap();
+    let queue = &mut save["event_queue"];
+    if let Some(actions) = queue["actions"].as_array_mut() {
+        actions.iter_mut().for_each(add_cause);
+    }
+    if let Some(timers) = queue["timers"].as_array_mut() {
+        for timer in timers.iter_mut() {
+            add_cause(&mut timer["action"]);
+        }
+    }
+    save
+}
+
+// Version 9 had no characters. They start out fresh, with their own timer.
+fn add_npcs(mut save: Value) -> Value {
+    save["npcs"] = json!({});
+    if let Some(timers) = save["event_queue"]["timers"].as_array_mut() {
+        timers.push(serde_json::to_value(npc_timer()).unwrap());
+    }
+    save
+}
+
+// Version 10 had an AI that never glitched.
+fn add_ai_integrity(mut save: Value) -> Value {
+    save["ai_integrity"] = FULL_INTEGRITY.into();
+    save
+}
+
+// Version 11 had no bridge. Only the doors in `locked_doors` stay locked on
+// load, so its door has to be added to be locked.
+fn lock_bridge(mut save: Value) -> Value {
+    if let Some(locked_doors) = save["locked_doors"].as_array_mut() {
+        locked_doors.push(json!(["commlink", "bridge"]));
+    }
+    save
+}
+
+// Version 12 had no memories. The player's name is known once the commlink
+// console has been used.
+fn add_memories(mut save: Value) -> Value {
+    save["player"]["memories"] = json!([]);
+    let contacted = save["flags"]
+        .as_array()
+        .map_or(false, |flags| flags.contains(&json!("bridge_contacted")));
+    save["player"]["name"] = if contacted { json!("Ellis Reyes") } else { Value::Null };
+    save
+}
+
+fn migrate(mut save: Value) -> Result<Value, SaveError> {
+    let version = save
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    if version == 0 || version > SAVE_VERSION {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+
+    for migration in &MIGRATIONS[(version - 1) as usize..] {
+        save = migration(save);
+    }
+    save["version"] = SAVE_VERSION.into();
+    Ok(save)
+}
+
+/// Every saved slot, the most recently saved first.
+pub fn slots() -> Vec<String> {
+    let entries = match fs::read_dir(SAVE_DIR) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    let mut slots: Vec<_> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            let modified = fs::metadata(&path).ok()?.modified().ok()?;
+            Some((modified, path.file_stem()?.to_str()?.to_string()))
+        })
+        .collect();
+    slots.sort_by(|a, b| b.cmp(a));
+    slots.into_iter().map(|(_, slot)| slot).collect()
+}
+
+/// The slot that was saved to most recently, if there is any.
+pub fn latest_slot() -> Option<String> {
+    slots().into_iter().next()
+}
+
+pub fn save_game(app: &mut App, slot: &str) -> Result<(), SaveError> {
+    let path = slot_path(slot)?;
+    let rng_seed = app.state.rng.gen();
+    app.state.rng = StdRng::seed_from_u64(rng_seed);
+
+    let save = SaveGame {
+        version: SAVE_VERSION,
+        current_room: app.state.current_room,
+        rng_seed: rng_seed,
+        player: app.state.player.clone(),
+        flags: app.state.flags.clone(),
+        items: app.state.item_locations.clone(),
+        npcs: app.state.npcs.clone(),
+        ai_integrity: app.state.ai_integrity,
+        stats: app.state.stats.clone(),
+        enemies: app
+            .state
+            .enemies
+            .iter()
+            .map(|(room_type, enemy)| (*room_type, enemy.get_health()))
+            .collect(),
+        rooms: app
+            .rooms
+            .iter()
+            .map(|(room_type, room)| (*room_type, room.save()))
+            .collect(),
+        locked_doors: app
+            .doors
+            .iter()
+            .filter(|door| door.locked)
+            .map(|door| (door.definition.from, door.definition.to))
+            .collect(),
+        event_queue: app.event_queue.clone(),
+        log: app.log.clone(),
+    };
+
+    fs::create_dir_all(SAVE_DIR)?;
+    fs::write(path, serde_json::to_string_pretty(&save)?)?;
+    Ok(())
+}
+
+pub fn load_game(app: &mut App, slot: &str) -> Result<(), SaveError> {
+    let path = slot_path(slot)?;
+    let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let save: SaveGame = serde_json::from_value(migrate(value)?)?;
+
+    // Everything that can fail happens before the game is touched, so a bad
+    // save leaves it as it was.
+    let mut rooms = vec![];
+    for (room_type, value) in save.rooms {
+        if let Some(room) = app.rooms.get(&room_type) {
+            rooms.push((room_type, room.load(value)?));
+        }
+    }
+    app.rooms.extend(rooms);
+
+    for door in app.doors.iter_mut() {
+        let key = (door.definition.from, door.definition.to);
+        door.locked = save.locked_doors.contains(&key);
+    }
+
+    // Enemies are rebuilt from their templates, only their health is saved.
+    let mut fresh_state = State::new(0);
+    initialize_enemies(&mut fresh_state, &app.rooms);
+    let enemies = save.enemies;
+    app.state.enemies = fresh_state
+        .enemies
+        .into_iter()
+        .filter_map(|(room_type, mut enemy)| {
+            let health = *enemies.get(&room_type)?;
+            enemy.set_health(health);
+            Some((room_type, enemy))
+        })
+        .collect();
+
+    app.state.current_room = save.current_room;
+    app.state.rng = StdRng::seed_from_u64(save.rng_seed);
+    app.state.player = save.player;
+    app.state.flags = save.flags;
+    // Items added to the game since the save start where they are defined.
+    initialize_items(&mut app.state, &app.items);
+    app.state.item_locations.extend(save.items);
+    initialize_npcs(&mut app.state, &app.npcs);
+    app.state.npcs.extend(save.npcs);
+    app.state.ai_integrity = save.ai_integrity;
+    app.state.stats = save.stats;
+    app.state.game_over = None;
+    app.state.conversation = None;
+    app.event_queue = save.event_queue;
+    app.log = save.log;
+    Ok(())
+}
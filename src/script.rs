@@ -0,0 +1,324 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rhai::{CallFnOptions, Dynamic, Engine, FuncArgs, Scope, AST, INT};
+
+use crate::action::Action;
+use crate::entities::{Item, ItemLocation};
+use crate::game_event::{GameEvent, GameEventType};
+use crate::timer::{Timer, TimerType};
+use crate::utils::invalid_data;
+use crate::App;
+
+/// Everything a script can see and change, bound to `this` in every hook.
+///
+/// Scripts work on a copy of the game; whatever they change is written back
+/// once the hook returns.
+#[derive(Debug, Clone)]
+pub struct ScriptContext {
+    room: String,
+    health: INT,
+    // What the player carries. Only items from `items.ron` can be given.
+    items: Vec<Item>,
+    known_items: Vec<Item>,
+    flags: BTreeSet<String>,
+    visited: BTreeSet<String>,
+    enemies: BTreeSet<String>,
+    rng: StdRng,
+    actions: Vec<Action>,
+    timers: Vec<Timer>,
+    log: Vec<GameEvent>,
+}
+
+impl ScriptContext {
+    fn new(app: &mut App) -> ScriptContext {
+        let state = &mut app.state;
+        ScriptContext {
+            room: state.current_room.to_string(),
+            health: state.player.health as INT,
+            items: state.carried_items(),
+            known_items: app.items.keys().cloned().collect(),
+            flags: state.flags.clone(),
+            visited: app
+                .rooms
+                .iter()
+                .filter(|(_, room)| room.is_visited())
+                .map(|(room_type, _)| room_type.to_string())
+                .collect(),
+            enemies: state.enemies.keys().map(|room_type| room_type.to_string()).collect(),
+            // Seeded from the game's RNG so replays stay deterministic.
+            rng: StdRng::seed_from_u64(state.rng.gen()),
+            actions: vec![],
+            timers: vec![],
+            log: vec![],
+        }
+    }
+
+    fn apply(self, app: &mut App) {
+        app.state.player.health = self.health as i32;
+        for item in app.state.carried_items() {
+            if !self.items.contains(&item) {
+                app.state.item_locations.insert(item, ItemLocation::Nowhere);
+            }
+        }
+        for item in self.items {
+            app.state.item_locations.insert(item, ItemLocation::Player);
+        }
+        app.state.flags = self.flags;
+        for event in self.log {
+            app.log.push_front(event);
+        }
+        app.event_queue.schedule_actions(self.actions);
+        app.event_queue.schedule_timers(self.timers);
+    }
+
+    fn debug(&mut self, message: String) {
+        self.log.push(GameEvent {
+            content: message + "\n",
+            game_event_type: GameEventType::Debug,
+        });
+    }
+
+    // Actions are written in the same RON notation as the data files.
+    fn parse_action(&mut self, action: &str) -> Option<Action> {
+        match ron::de::from_str(action) {
+            Ok(action) => Some(action),
+            Err(err) => {
+                self.debug(format!("Script scheduled invalid action {}: {}", action, err));
+                None
+            }
+        }
+    }
+
+    fn has_item(&mut self, id: &str) -> bool {
+        self.items.contains(&Item::from(id))
+    }
+
+    fn give_item(&mut self, id: &str) {
+        let item = Item::from(id);
+        if !self.known_items.contains(&item) {
+            self.debug(format!("Script gave unknown item {}", id));
+        } else if !self.items.contains(&item) {
+            self.items.push(item);
+        }
+    }
+
+    fn take_item(&mut self, id: &str) {
+        let item = Item::from(id);
+        self.items.retain(|owned| *owned != item);
+    }
+
+    fn log(&mut self, message: &str, kind: &str) {
+        let game_event_type = match kind {
+            "success" => GameEventType::Success,
+            "failure" => GameEventType::Failure,
+            "combat" => GameEventType::Combat,
+            "debug" => GameEventType::Debug,
+            "ai" => GameEventType::Ai,
+            _ => GameEventType::Normal,
+        };
+        self.log.push(GameEvent {
+            content: format!("{}\n", message),
+            game_event_type,
+        });
+    }
+
+    fn schedule(&mut self, action: &str) {
+        if let Some(action) = self.parse_action(action) {
+            self.actions.push(action);
+        }
+    }
+
+    fn schedule_timer(&mut self, label: &str, msecs: INT, action: &str) {
+        if let Some(action) = self.parse_action(action) {
+            self.timers.push(Timer::new(
+                TimerType::Script,
+                label,
+                0,
+                msecs.max(0) as u64,
+                action,
+                true,
+            ));
+        }
+    }
+}
+
+fn new_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .register_type_with_name::<ScriptContext>("Game")
+        .register_get("room", |ctx: &mut ScriptContext| ctx.room.clone())
+        .register_get_set(
+            "health",
+            |ctx: &mut ScriptContext| ctx.health,
+            |ctx: &mut ScriptContext, health: INT| ctx.health = health,
+        )
+        .register_fn("has_item", ScriptContext::has_item)
+        .register_fn("give_item", ScriptContext::give_item)
+        .register_fn("take_item", ScriptContext::take_item)
+        .register_fn("flag", |ctx: &mut ScriptContext, flag: &str| {
+            ctx.flags.contains(flag)
+        })
+        .register_fn("set_flag", |ctx: &mut ScriptContext, flag: &str| {
+            ctx.flags.insert(flag.to_string());
+        })
+        .register_fn("clear_flag", |ctx: &mut ScriptContext, flag: &str| {
+            ctx.flags.remove(flag);
+        })
+        .register_fn("visited", |ctx: &mut ScriptContext, room: &str| {
+            ctx.visited.contains(room)
+        })
+        .register_fn("enemy_in", |ctx: &mut ScriptContext, room: &str| {
+            ctx.enemies.contains(room)
+        })
+        .register_fn("random", |ctx: &mut ScriptContext, below: INT| {
+            if below > 0 {
+                ctx.rng.gen_range(0, below)
+            } else {
+                0
+            }
+        })
+        .register_fn("log", |ctx: &mut ScriptContext, message: &str| {
+            ctx.log(message, "normal")
+        })
+        .register_fn("log", ScriptContext::log)
+        .register_fn("message", |ctx: &mut ScriptContext, message: &str| {
+            ctx.actions
+                .push(Action::Message(message.into(), GameEventType::Normal));
+        })
+        .register_fn("schedule", ScriptContext::schedule)
+        .register_fn("schedule_timer", ScriptContext::schedule_timer);
+    engine
+}
+
+/// The scripting runtime and every script in `assets/scripts/`, by file name.
+#[derive(Debug)]
+pub struct Scripts {
+    engine: Engine,
+    asts: HashMap<String, AST>,
+}
+
+impl Default for Scripts {
+    fn default() -> Self {
+        Scripts {
+            engine: new_engine(),
+            asts: HashMap::new(),
+        }
+    }
+}
+
+impl Scripts {
+    /// Compile every `*.rhai` file in `dir`, so syntax errors show up at startup.
+    pub fn load(dir: &Path) -> io::Result<Scripts> {
+        let mut scripts = Scripts::default();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(true, |extension| extension != "rhai") {
+                continue;
+            }
+
+            let name = match path.file_name().and_then(|name| name.to_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+            let ast = scripts
+                .engine
+                .compile(&fs::read_to_string(&path)?)
+                .map_err(|err| invalid_data(&path, err))?;
+            scripts.asts.insert(name, ast);
+        }
+        Ok(scripts)
+    }
+
+    pub fn contains(&self, script: &str) -> bool {
+        self.asts.contains_key(script)
+    }
+
+    fn has_hook(&self, script: &str, hook: &str, arity: usize) -> bool {
+        self.asts.get(script).map_or(false, |ast| {
+            ast.iter_functions()
+                .any(|function| function.name == hook && function.params.len() == arity)
+        })
+    }
+}
+
+/// Call `hook` in `script` if the script defines it.
+///
+/// Returns whether the hook returned `true`, meaning it took care of the
+/// action and the usual handling should be skipped.
+pub fn run_hook<A: FuncArgs>(
+    app: &mut App,
+    script: &str,
+    hook: &str,
+    arity: usize,
+    args: A,
+) -> bool {
+    if !app.scripts.has_hook(script, hook, arity) {
+        return false;
+    }
+
+    let mut this = Dynamic::from(ScriptContext::new(app));
+    let result = app.scripts.engine.call_fn_with_options::<Dynamic>(
+        CallFnOptions::new().bind_this_ptr(&mut this),
+        &mut Scope::new(),
+        &app.scripts.asts[script],
+        hook,
+        args,
+    );
+
+    let mut ctx = this.cast::<ScriptContext>();
+    let handled = match result {
+        Ok(value) => value.as_bool().unwrap_or(false),
+        Err(err) => {
+            ctx.debug(format!("Script error in {} {}: {}", script, hook, err));
+            false
+        }
+    };
+    ctx.apply(app);
+    handled
+}
+
+fn room_script(app: &App) -> Option<String> {
+    app.rooms
+        .get(&app.state.current_room)
+        .and_then(|room| room.definition().script.clone())
+}
+
+fn item_script(app: &App, item: Item) -> Option<String> {
+    app.items
+        .get(&item)
+        .and_then(|definition| definition.script.clone())
+}
+
+pub fn run_room_hook<A: FuncArgs>(app: &mut App, hook: &str, arity: usize, args: A) -> bool {
+    match room_script(app) {
+        Some(script) => run_hook(app, &script, hook, arity, args),
+        None => false,
+    }
+}
+
+/// Let the item and then the current room react to using `item`.
+pub fn run_use_hooks(app: &mut App, item: Item) -> bool {
+    let room = app.state.current_room.to_string();
+    if let Some(script) = item_script(app, item) {
+        if run_hook(app, &script, "on_use", 1, (room,)) {
+            return true;
+        }
+    }
+    run_room_hook(app, "on_use", 1, (item.id().to_string(),))
+}
+
+/// Tick the current room and everything the player carries.
+pub fn run_tick_hooks(app: &mut App, dt: u64) {
+    let dt = dt as INT;
+    run_room_hook(app, "on_tick", 1, (dt,));
+    for item in app.state.carried_items() {
+        if let Some(script) = item_script(app, item) {
+            run_hook(app, &script, "on_tick", 1, (dt,));
+        }
+    }
+}
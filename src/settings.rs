@@ -0,0 +1,82 @@
+use std::fs;
+use std::io;
+
+static SETTINGS_FILE: &'static str = "settings.json";
+
+pub const MAX_VOLUME: u8 = 10;
+
+/// How fast new log entries are typed out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum TextSpeed {
+    Slow,
+    Normal,
+    Fast,
+    Instant,
+}
+
+impl TextSpeed {
+    /// Characters revealed per second, `None` for all at once.
+    pub fn chars_per_second(&self) -> Option<u64> {
+        match self {
+            TextSpeed::Slow => Some(30),
+            TextSpeed::Normal => Some(80),
+            TextSpeed::Fast => Some(200),
+            TextSpeed::Instant => None,
+        }
+    }
+
+    pub fn faster(&self) -> TextSpeed {
+        match self {
+            TextSpeed::Slow => TextSpeed::Normal,
+            TextSpeed::Normal => TextSpeed::Fast,
+            _ => TextSpeed::Instant,
+        }
+    }
+
+    pub fn slower(&self) -> TextSpeed {
+        match self {
+            TextSpeed::Instant => TextSpeed::Fast,
+            TextSpeed::Fast => TextSpeed::Normal,
+            _ => TextSpeed::Slow,
+        }
+    }
+}
+
+/// Player preferences, kept across games.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    // From 0 to `MAX_VOLUME`.
+    pub volume: u8,
+    pub text_speed: TextSpeed,
+    // Off renders all text in the terminal's default colors.
+    pub colors: bool,
+    // On runs misspelled commands with the typo fixed instead of only
+    // suggesting the fix.
+    pub autocorrect: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            volume: MAX_VOLUME,
+            text_speed: TextSpeed::Instant,
+            colors: true,
+            autocorrect: false,
+        }
+    }
+}
+
+impl Settings {
+    /// The saved settings, or the defaults if there are none yet.
+    pub fn load() -> Self {
+        fs::read_to_string(SETTINGS_FILE)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(SETTINGS_FILE, serde_json::to_string_pretty(self)?)
+    }
+}
@@ -11,15 +11,19 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use strum::IntoEnumIterator;
 
+use crate::settings::MAX_VOLUME;
+
 static FADE_SAMPLES: u32 = 44_100;
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum AudioEvent {
     Effect(Effect),
     Track(Track),
+    // From 0 to `MAX_VOLUME`.
+    Volume(u8),
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
 pub enum Effect {
     Typing,
     BeepLong,
@@ -29,7 +33,7 @@ pub enum Effect {
     Door,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, EnumIter, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, EnumIter, Hash, Serialize, Deserialize)]
 pub enum Track {
     Intro,
     Complications,
@@ -181,13 +185,14 @@ impl AudioEvent {
                 Track::Complications => &include_bytes!("../assets/music/complications.mp3")[..],
                 Track::Loop => &include_bytes!("../assets/music/loop.mp3")[..],
             },
+            AudioEvent::Volume(_) => unreachable!("volume changes have no sound"),
         }
     }
 }
 
 pub fn start(recv: Receiver<AudioEvent>) {
     let device = rodio::default_output_device().unwrap();
-    let sink = Sink::new(&device);
+    let mut sink = Sink::new(&device);
     let (effect_mixer_controller, effect_mixer): (
         std::sync::Arc<rodio::dynamic_mixer::DynamicMixerController<i16>>,
         rodio::dynamic_mixer::DynamicMixer<i16>,
@@ -205,6 +210,7 @@ pub fn start(recv: Receiver<AudioEvent>) {
                 effect_mixer_controller.add(source);
             }
             AudioEvent::Track(ref track) => music_controller.set_track(*track),
+            AudioEvent::Volume(volume) => sink.set_volume(volume as f32 / MAX_VOLUME as f32),
         }
     }
 }
@@ -1,29 +1,79 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::ai::FULL_INTEGRITY;
+use crate::dialogue::Conversation;
 use crate::entities::enemy::Enemy;
+use crate::entities::item::{Item, ItemLocation};
+use crate::entities::npc::{Npc, NpcState};
 use crate::entities::player::Player;
-use crate::room::RoomType;
+use crate::game_over::{GameOver, RunStats};
+use crate::oxygen::MAX_OXYGEN;
+use crate::room::{RoomType, START_ROOM};
 
 #[derive(Debug)]
 pub struct State {
     pub current_room: RoomType,
     pub player: Player,
     pub enemies: HashMap<RoomType, Box<Enemy>>,
+    // Story progress set by triggers.
+    pub flags: BTreeSet<String>,
+    // Where every item is, including the ones the player carries.
+    pub item_locations: HashMap<Item, ItemLocation>,
+    pub npcs: HashMap<Npc, NpcState>,
+    // How well the ship's AI still holds together, see `ai::corrupt`.
+    pub ai_integrity: i32,
+    pub stats: RunStats,
+    // Set while talking to someone. Not saved, loading ends the conversation.
+    pub conversation: Option<Conversation>,
+    // Set once the game has ended. Nothing but loading happens after that.
+    pub game_over: Option<GameOver>,
+    // All randomness goes through here so a seed reproduces a whole session.
+    pub rng: StdRng,
 }
 
 impl State {
-    pub fn new() -> Self {
+    pub fn new(seed: u64) -> Self {
         State {
-            current_room: RoomType::Cryobay,
+            current_room: START_ROOM,
             player: Player {
                 health: 100,
                 attack_strength: 5,
-                items: vec![],
+                oxygen: MAX_OXYGEN,
+                status_effects: vec![],
+                memories: vec![],
+                name: None,
             },
             enemies: HashMap::new(),
+            flags: BTreeSet::new(),
+            item_locations: HashMap::new(),
+            npcs: HashMap::new(),
+            ai_integrity: FULL_INTEGRITY,
+            stats: RunStats::default(),
+            conversation: None,
+            game_over: None,
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 
+    pub fn has_item(&self, item: Item) -> bool {
+        self.item_locations.get(&item) == Some(&ItemLocation::Player)
+    }
+
+    // Sorted, so listings don't depend on hash order.
+    pub fn carried_items(&self) -> Vec<Item> {
+        let mut items: Vec<Item> = self
+            .item_locations
+            .iter()
+            .filter(|(_, location)| **location == ItemLocation::Player)
+            .map(|(item, _)| *item)
+            .collect();
+        items.sort();
+        items
+    }
+
     pub fn get_current_enemy(&self, room_type: RoomType) -> Option<&Box<Enemy>> {
         self.enemies.get(&room_type)
     }
@@ -1,14 +1,16 @@
 use crate::action::Action;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TimerType {
     EnemyAttack,
     Oxygen,
     Storytime,
     Reboot,
+    Script,
+    Npc,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Timer {
     pub timer_type: TimerType,
     pub label: String,
@@ -41,15 +43,17 @@ impl Timer {
         self.elapsed += dt;
     }
 
+    /// How far along the timer is, for its gauge. Stays at 100 once done.
     pub fn current_percent(&self) -> u8 {
         if self.duration != 0 {
-            (self.elapsed * 100 / self.duration) as u8
+            (self.elapsed * 100 / self.duration).min(100) as u8
         } else {
             0
         }
     }
 
+    // Compared in milliseconds, a single long tick can overshoot by any amount.
     pub fn is_done(&self) -> bool {
-        self.current_percent() > 100
+        self.elapsed > self.duration
     }
 }
@@ -0,0 +1,165 @@
+use termion::event::Key;
+use tui::style::{Color, Modifier, Style};
+use tui::widgets::Text;
+
+use crate::menu::{change_setting, format_entries, settings_entries, SETTINGS_ENTRIES};
+use fredjam2018::ending::{unlocked_endings, Ending};
+use fredjam2018::save::slots;
+use fredjam2018::settings::Settings;
+
+/// What the player picked on the title screen.
+pub enum TitleChoice {
+    NewGame,
+    Load(String),
+    // A setting was changed and should be applied and saved.
+    Settings,
+    Quit,
+}
+
+enum Page {
+    Main,
+    Load,
+    Settings,
+}
+
+static MAIN_ENTRIES: [&'static str; 5] = ["New Game", "Continue", "Load", "Settings", "Quit"];
+
+const CONTINUE: usize = 1;
+
+pub struct TitleMenu {
+    page: Page,
+    selected: usize,
+    // Newest first, so the first one is what Continue loads.
+    slots: Vec<String>,
+    // Ids of the endings reached in earlier games.
+    unlocked_endings: Vec<String>,
+    // Why the settings could not be saved, shown below the entries.
+    pub status: Option<String>,
+}
+
+impl TitleMenu {
+    pub fn new() -> Self {
+        TitleMenu {
+            page: Page::Main,
+            selected: 0,
+            slots: slots(),
+            unlocked_endings: unlocked_endings(),
+            status: None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.page {
+            Page::Main => MAIN_ENTRIES.len(),
+            // Every slot plus the way back.
+            Page::Load => self.slots.len() + 1,
+            Page::Settings => SETTINGS_ENTRIES,
+        }
+    }
+
+    fn open(&mut self, page: Page) {
+        self.page = page;
+        self.selected = 0;
+    }
+
+    pub fn handle_key(&mut self, key: Key, settings: &mut Settings) -> Option<TitleChoice> {
+        match key {
+            Key::Up => self.selected = (self.selected + self.len() - 1) % self.len(),
+            Key::Down => self.selected = (self.selected + 1) % self.len(),
+            Key::Left | Key::Right => {
+                if let Page::Settings = self.page {
+                    if change_setting(self.selected, key == Key::Right, settings) {
+                        return Some(TitleChoice::Settings);
+                    }
+                }
+            }
+            Key::Char('\n') => return self.select(settings),
+            Key::Esc => match self.page {
+                Page::Main => return Some(TitleChoice::Quit),
+                _ => self.open(Page::Main),
+            },
+            _ => {}
+        }
+        None
+    }
+
+    fn select(&mut self, settings: &mut Settings) -> Option<TitleChoice> {
+        match self.page {
+            Page::Main => match self.selected {
+                0 => return Some(TitleChoice::NewGame),
+                CONTINUE => return self.slots.first().cloned().map(TitleChoice::Load),
+                2 => self.open(Page::Load),
+                3 => self.open(Page::Settings),
+                _ => return Some(TitleChoice::Quit),
+            },
+            Page::Load => match self.slots.get(self.selected) {
+                Some(slot) => return Some(TitleChoice::Load(slot.clone())),
+                None => self.open(Page::Main),
+            },
+            Page::Settings => {
+                if self.selected == SETTINGS_ENTRIES - 1 {
+                    self.open(Page::Main);
+                } else if change_setting(self.selected, true, settings) {
+                    return Some(TitleChoice::Settings);
+                }
+            }
+        }
+        None
+    }
+
+    fn entries(&self, settings: &Settings) -> Vec<String> {
+        match self.page {
+            Page::Main => MAIN_ENTRIES.iter().map(|entry| entry.to_string()).collect(),
+            Page::Load => self
+                .slots
+                .iter()
+                .cloned()
+                .chain(Some(String::from("Back")))
+                .collect(),
+            Page::Settings => settings_entries(settings),
+        }
+    }
+
+    pub fn format(&self, settings: &Settings, endings: &[Ending]) -> Vec<Text> {
+        let disabled = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![
+            Text::styled(
+                "S C   C Y C L O N E\n\n",
+                Style::default().fg(Color::Cyan).modifier(Modifier::Bold),
+            ),
+            Text::raw("Use the arrow keys to choose and Enter to confirm.\n\n"),
+        ];
+
+        let is_disabled = |index| match self.page {
+            Page::Main => index == CONTINUE && self.slots.is_empty(),
+            _ => false,
+        };
+        lines.extend(format_entries(self.entries(settings), self.selected, is_disabled));
+        if let Page::Load = self.page {
+            if self.slots.is_empty() {
+                lines.push(Text::styled("\nThere are no saved games yet.\n", disabled));
+            }
+        }
+        if let Some(ref status) = self.status {
+            lines.push(Text::styled(format!("\n{}\n", status), Style::default().fg(Color::Red)));
+        }
+
+        let is_unlocked = |ending: &Ending| self.unlocked_endings.contains(&ending.id);
+        lines.push(Text::raw(format!(
+            "\nEndings found: {}/{}\n",
+            endings.iter().filter(|ending| is_unlocked(ending)).count(),
+            endings.len()
+        )));
+        for ending in endings {
+            if is_unlocked(ending) {
+                lines.push(Text::styled(
+                    format!("  {}\n", ending.title),
+                    Style::default().fg(Color::Green),
+                ));
+            } else {
+                lines.push(Text::styled("  ???\n", disabled));
+            }
+        }
+        lines
+    }
+}
@@ -0,0 +1,213 @@
+use std::io;
+use std::path::Path;
+
+use crate::action::{Action, ActionHandled};
+use crate::ai::{damage_ai, say};
+use crate::entities::{is_alive, Item, ItemLocation, Npc, StatusEffectType};
+use crate::room::RoomType;
+use crate::timer::{Timer, TimerType};
+use crate::utils::load_ron;
+use crate::App;
+
+/// Something about the game that a trigger can check.
+#[derive(Debug, Clone, Deserialize)]
+pub enum Condition {
+    InRoom(RoomType),
+    Visited(RoomType),
+    // A living enemy guards the room.
+    Enemy(RoomType),
+    HasItem(Item),
+    // The item lies in the room, outside of any container.
+    ItemInRoom(Item, RoomType),
+    Flag(String),
+    // A timer of this type is running.
+    Timer(TimerType),
+    DoorLocked(RoomType, RoomType),
+    Status(StatusEffectType),
+    NpcAlive(Npc),
+    // The character is in the same room as the player.
+    NpcHere(Npc),
+    NpcStatus(Npc, StatusEffectType),
+    NpcHealthBelow(Npc, i32),
+    NpcFollowing(Npc),
+    AiIntegrityBelow(i32),
+    // The player has recovered this memory.
+    Remembers(String),
+    // The player has recovered at least this many memories.
+    RemembersAtLeast(usize),
+    Not(Box<Condition>),
+}
+
+/// What a trigger does once it fires.
+#[derive(Debug, Clone, Deserialize)]
+pub enum Effect {
+    Schedule(Action),
+    StartTimer(Timer),
+    SetFlag(String),
+    ClearFlag(String),
+    Unlock(RoomType, RoomType),
+    AddStatus(StatusEffectType, u64),
+    // Use up an item, wherever it is.
+    RemoveItem(Item),
+    HealNpc(Npc, i32),
+    CureNpc(Npc, StatusEffectType),
+    // Have the character follow the player around, or stay where it is.
+    Follow(Npc),
+    Stay(Npc),
+    // Have the ship's AI say a line from `assets/ai.ron`.
+    AiSays(String),
+    DamageAi(i32),
+    // The player finds out their name.
+    LearnName(String),
+}
+
+/// "When `on` happens in `room` and all `conditions` hold, apply `effects`."
+#[derive(Debug, Clone, Deserialize)]
+pub struct Trigger {
+    // `Enter` only fires once the player gets through the door, before the
+    // room counts as visited.
+    pub on: Action,
+    // Where the player has to be, anywhere if left out.
+    #[serde(default)]
+    pub room: Option<RoomType>,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    pub effects: Vec<Effect>,
+    // Whether the action is used up, keeping the global handlers from seeing it.
+    #[serde(default)]
+    pub handled: bool,
+}
+
+pub fn load_triggers(path: &Path) -> io::Result<Vec<Trigger>> {
+    load_ron(path)
+}
+
+pub fn holds(app: &App, condition: &Condition) -> bool {
+    match condition {
+        Condition::InRoom(room_type) => app.state.current_room == *room_type,
+        Condition::Visited(room_type) => app
+            .rooms
+            .get(room_type)
+            .map_or(false, |room| room.is_visited()),
+        Condition::Enemy(room_type) => app.state.enemies.contains_key(room_type),
+        Condition::HasItem(item) => app.state.has_item(*item),
+        Condition::ItemInRoom(item, room_type) => {
+            app.state.item_locations.get(item) == Some(&ItemLocation::Room(*room_type))
+        }
+        Condition::Flag(flag) => app.state.flags.contains(flag),
+        Condition::Timer(timer_type) => app
+            .event_queue
+            .timers
+            .iter()
+            .any(|timer| timer.timer_type == *timer_type),
+        Condition::DoorLocked(from, to) => app.doors.iter().any(|door| {
+            door.locked && door.definition.from == *from && door.definition.to == *to
+        }),
+        Condition::Status(effect_type) => app.state.player.has_status_effect(*effect_type),
+        Condition::NpcAlive(npc) => is_alive(app, *npc),
+        Condition::NpcHere(npc) => app
+            .state
+            .npcs
+            .get(npc)
+            .map_or(false, |npc_state| npc_state.room == app.state.current_room),
+        Condition::NpcStatus(npc, effect_type) => app
+            .state
+            .npcs
+            .get(npc)
+            .map_or(false, |npc_state| npc_state.status_effects.contains(effect_type)),
+        Condition::NpcHealthBelow(npc, health) => app
+            .state
+            .npcs
+            .get(npc)
+            .map_or(false, |npc_state| npc_state.health < *health),
+        Condition::NpcFollowing(npc) => app
+            .state
+            .npcs
+            .get(npc)
+            .map_or(false, |npc_state| npc_state.following),
+        Condition::AiIntegrityBelow(integrity) => app.state.ai_integrity < *integrity,
+        Condition::Remembers(id) => app.state.player.memories.contains(id),
+        Condition::RemembersAtLeast(count) => app.state.player.memories.len() >= *count,
+        Condition::Not(condition) => !holds(app, condition),
+    }
+}
+
+fn set_following(app: &mut App, npc: Npc, following: bool) {
+    if let Some(npc_state) = app.state.npcs.get_mut(&npc) {
+        // The dead go nowhere.
+        npc_state.following = following && npc_state.is_alive();
+    }
+}
+
+pub fn apply(app: &mut App, effect: Effect) {
+    match effect {
+        Effect::Schedule(action) => app.event_queue.schedule_action(action),
+        Effect::StartTimer(timer) => app.event_queue.schedule_timer(timer),
+        Effect::SetFlag(flag) => {
+            app.state.flags.insert(flag);
+        }
+        Effect::ClearFlag(flag) => {
+            app.state.flags.remove(&flag);
+        }
+        Effect::AddStatus(effect_type, duration) => {
+            app.state.player.add_status_effect(effect_type, duration);
+        }
+        Effect::RemoveItem(item) => {
+            app.state.item_locations.insert(item, ItemLocation::Nowhere);
+        }
+        Effect::HealNpc(npc, amount) => {
+            let max_health = app.npcs.get(&npc).map_or(0, |definition| definition.health);
+            if let Some(npc_state) = app.state.npcs.get_mut(&npc) {
+                if npc_state.is_alive() {
+                    npc_state.health = (npc_state.health + amount).min(max_health);
+                }
+            }
+        }
+        Effect::CureNpc(npc, effect_type) => {
+            if let Some(npc_state) = app.state.npcs.get_mut(&npc) {
+                npc_state.status_effects.retain(|effect| *effect != effect_type);
+            }
+        }
+        Effect::Follow(npc) => set_following(app, npc, true),
+        Effect::Stay(npc) => set_following(app, npc, false),
+        Effect::AiSays(id) => say(app, &id),
+        Effect::DamageAi(amount) => damage_ai(app, amount),
+        Effect::LearnName(name) => app.state.player.name = Some(name),
+        Effect::Unlock(from, to) => {
+            for door in app.doors.iter_mut() {
+                if door.definition.from == from && door.definition.to == to {
+                    door.locked = false;
+                }
+            }
+        }
+    }
+}
+
+/// Fire every trigger matching `action`, in the order they are declared.
+pub fn run_triggers(app: &mut App, action: &Action) -> ActionHandled {
+    // Conditions are all checked up front, so one trigger's effects can't
+    // enable another for the same action.
+    let fired: Vec<Trigger> = app
+        .triggers
+        .iter()
+        .filter(|trigger| {
+            trigger.on == *action
+                && trigger
+                    .room
+                    .map_or(true, |room| room == app.state.current_room)
+                && trigger.conditions.iter().all(|condition| holds(app, condition))
+        })
+        .cloned()
+        .collect();
+
+    let mut handled = ActionHandled::NotHandled;
+    for trigger in fired {
+        if trigger.handled {
+            handled = ActionHandled::Handled;
+        }
+        for effect in trigger.effects {
+            apply(app, effect);
+        }
+    }
+    handled
+}
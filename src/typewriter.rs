@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+
+use fredjam2018::game_event::GameEvent;
+use fredjam2018::settings::TextSpeed;
+
+/// Types out new log entries a few characters at a time.
+pub struct Typewriter {
+    pub speed: TextSpeed,
+    // Characters at the newest end of the log that are not shown yet.
+    hidden: usize,
+    // Milliseconds since the last character was revealed.
+    elapsed: u64,
+}
+
+impl Typewriter {
+    pub fn new(speed: TextSpeed) -> Self {
+        Typewriter {
+            speed,
+            hidden: 0,
+            elapsed: 0,
+        }
+    }
+
+    /// Hide freshly written entries so they can be typed out.
+    pub fn write(&mut self, game_events: &[GameEvent]) {
+        if self.speed.chars_per_second().is_some() {
+            self.hidden += game_events
+                .iter()
+                .map(|game_event| game_event.content.chars().count())
+                .sum::<usize>();
+        }
+    }
+
+    pub fn tick(&mut self, dt: u64) {
+        let chars_per_second = match self.speed.chars_per_second() {
+            Some(chars_per_second) => chars_per_second,
+            None => return self.finish(),
+        };
+        if self.hidden == 0 {
+            self.elapsed = 0;
+            return;
+        }
+        self.elapsed += dt;
+        let revealed = self.elapsed * chars_per_second / 1000;
+        self.elapsed -= revealed * 1000 / chars_per_second;
+        self.hidden = self.hidden.saturating_sub(revealed as usize);
+    }
+
+    /// Show everything right away.
+    pub fn finish(&mut self) {
+        self.hidden = 0;
+        self.elapsed = 0;
+    }
+
+    /// The log, newest first, with every entry cut down to what is typed so far.
+    pub fn visible<'a>(&self, log: &'a VecDeque<GameEvent>) -> Vec<(&'a GameEvent, &'a str)> {
+        let mut hidden = self.hidden;
+        let mut visible = vec![];
+        for game_event in log {
+            let len = game_event.content.chars().count();
+            if hidden >= len {
+                hidden -= len;
+                continue;
+            }
+            let end = game_event
+                .content
+                .char_indices()
+                .nth(len - hidden)
+                .map_or(game_event.content.len(), |(index, _)| index);
+            visible.push((game_event, &game_event.content[..end]));
+            hidden = 0;
+        }
+        visible
+    }
+}
@@ -1,13 +1,89 @@
 use itertools::Itertools;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Duration;
 use tui::layout::Rect;
 use tui::style::Color;
 use tui::widgets::canvas::{Line, Shape};
 
+use crate::action::Action;
+use crate::game_event::GameEventType;
+use crate::App;
+
+// Game data is read from the source tree at runtime.
+pub fn assets_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")
+}
+
+/// What is wrong with the data file at `path`.
+pub fn invalid_data<E: fmt::Display>(path: &Path, err: E) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), err),
+    )
+}
+
+/// Read a data file written in RON.
+pub fn load_ron<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    ron::de::from_str(&fs::read_to_string(path)?).map_err(|err| invalid_data(path, err))
+}
+
+/// Log a message once the actions already queued have run.
+pub fn message(app: &mut App, message: String, game_event_type: GameEventType) {
+    app.event_queue
+        .schedule_action(Action::Message(message, game_event_type));
+}
+
+// Ids from data files live for the whole game, so they can be `Copy` handles.
+pub fn intern(id: &str) -> &'static str {
+    static IDS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    let mut ids = IDS.lock().unwrap();
+    if let Some(interned) = ids.get(id) {
+        return interned;
+    }
+    let interned: &'static str = Box::leak(id.to_string().into_boxed_str());
+    ids.insert(interned);
+    interned
+}
+
 pub fn duration_to_msec_u64(duration: &Duration) -> u64 {
     duration.as_secs() * 1000 + duration.subsec_millis() as u64
 }
 
+/// How many single-character edits turn `a` into `b`. Swapping two neighbors
+/// counts as one edit, since that is the most common typo.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // `distances[i][j]` is the distance between the first i and j characters.
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in 0..=a.len() {
+        distances[i][0] = i;
+    }
+    for j in 0..=b.len() {
+        distances[0][j] = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+    distances[a.len()][b.len()]
+}
+
 pub struct BoxShape {
     pub rect: Rect,
     pub color: Color,